@@ -0,0 +1,224 @@
+use leaders_array::leaders::LeaderScan;
+
+/// Algorithm computing the water trapped by a 1D elevation profile.
+pub trait TrapAlgorithm {
+    fn name(&self) -> &'static str;
+    fn trap(&self, height: &[i64]) -> i64;
+}
+
+/// Walks the left-to-right and right-to-left leaders (bars at least as high
+/// as everything before them), the water over each index being bounded by
+/// the closest leader on each side. This is the algorithm behind
+/// [`crate::Solution::trap`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Leaders;
+
+impl TrapAlgorithm for Leaders {
+    fn name(&self) -> &'static str {
+        "leaders"
+    }
+
+    fn trap(&self, height: &[i64]) -> i64 {
+        let mut sum = 0;
+
+        let mut l_edges = LeaderScan::new().non_strict().left_to_right().pairs(height);
+        let mut r_edges = LeaderScan::new().non_strict().right_to_left().pairs(height);
+        let mut l_ind = 0;
+        let mut r_ind = 0;
+
+        // Only reached past the last bar, which holds no water: the sentinel
+        // has its height so that the bound never exceeds it.
+        let last = height.last().copied().unwrap_or(0);
+        l_edges.push((height.len(), last));
+        r_edges.push((height.len(), last));
+
+        for (i, h) in height.iter().enumerate() {
+            if l_ind >= l_edges.len() - 1 {
+                break;
+            }
+            if i >= l_edges[l_ind + 1].0 {
+                l_ind += 1;
+            }
+            if i >= r_edges[r_ind].0 {
+                r_ind += 1;
+            }
+            if i < l_edges[l_ind].0 {
+                continue;
+            }
+            sum += std::cmp::max(std::cmp::min(l_edges[l_ind].1, r_edges[r_ind].1) - h, 0);
+        }
+
+        sum
+    }
+}
+
+/// Moves inwards from the lower of the two ends: the water over it is bounded
+/// by the highest bar seen on its own side.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TwoPointer;
+
+impl TrapAlgorithm for TwoPointer {
+    fn name(&self) -> &'static str {
+        "two pointer"
+    }
+
+    fn trap(&self, height: &[i64]) -> i64 {
+        let mut sum = 0;
+        let (mut l, mut r) = (0, height.len());
+        let (mut l_max, mut r_max) = (i64::MIN, i64::MIN);
+        while l < r {
+            if height[l] <= height[r - 1] {
+                l_max = l_max.max(height[l]);
+                sum += l_max - height[l];
+                l += 1;
+            } else {
+                r_max = r_max.max(height[r - 1]);
+                sum += r_max - height[r - 1];
+                r -= 1;
+            }
+        }
+        sum
+    }
+}
+
+/// Keeps a stack of decreasing bars; a higher bar closes the basin over the
+/// popped one, filling it layer by layer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonotonicStack;
+
+impl TrapAlgorithm for MonotonicStack {
+    fn name(&self) -> &'static str {
+        "monotonic stack"
+    }
+
+    fn trap(&self, height: &[i64]) -> i64 {
+        let mut sum = 0;
+        let mut stack: Vec<usize> = Vec::new();
+        for (i, h) in height.iter().enumerate() {
+            while let Some(&bottom) = stack.last() {
+                if height[bottom] >= *h {
+                    break;
+                }
+                stack.pop();
+                let Some(&left) = stack.last() else {
+                    break;
+                };
+                let depth = height[left].min(*h) - height[bottom];
+                sum += depth * (i - left - 1) as i64;
+            }
+            stack.push(i);
+        }
+        sum
+    }
+}
+
+pub fn all_algorithms() -> Vec<Box<dyn TrapAlgorithm>> {
+    vec![
+        Box::new(Leaders),
+        Box::new(TwoPointer),
+        Box::new(MonotonicStack),
+    ]
+}
+
+/// Input on which some algorithms disagree, with every algorithm's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub height: Vec<i64>,
+    pub results: Vec<(&'static str, i64)>,
+}
+
+/// Runs every algorithm on every input, returning the first input on which
+/// they do not all agree.
+pub fn find_disagreement<I>(
+    algorithms: &[Box<dyn TrapAlgorithm>],
+    inputs: I,
+) -> Option<Disagreement>
+where
+    I: IntoIterator<Item = Vec<i64>>,
+{
+    for height in inputs {
+        let results: Vec<(&'static str, i64)> = algorithms
+            .iter()
+            .map(|a| (a.name(), a.trap(&height)))
+            .collect();
+        if results.windows(2).any(|w| w[0].1 != w[1].1) {
+            return Some(Disagreement { height, results });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_examples() {
+        for algorithm in all_algorithms() {
+            assert_eq!(algorithm.trap(&[0, 1, 0, 2, 1, 0, 1, 3, 2, 1, 2, 1]), 6);
+            assert_eq!(
+                algorithm.trap(&[4, 2, 0, 3, 2, 5]),
+                9,
+                "{}",
+                algorithm.name()
+            );
+            assert_eq!(algorithm.trap(&[]), 0);
+        }
+    }
+
+    #[test]
+    fn heights_beyond_i32() {
+        let big = 4_000_000_000_000;
+        for algorithm in all_algorithms() {
+            assert_eq!(algorithm.trap(&[big, 0, 0, big]), 2 * big);
+        }
+    }
+
+    #[test]
+    fn negative_heights() {
+        for algorithm in all_algorithms() {
+            assert_eq!(algorithm.trap(&[-5]), 0, "{}", algorithm.name());
+            assert_eq!(algorithm.trap(&[-2, -5, -1]), 3, "{}", algorithm.name());
+        }
+    }
+
+    #[test]
+    fn randomized_differential() {
+        let mut rng = Rng::new(41);
+        let inputs = (0..2000).map(|i| {
+            let len = rng.below(30) as usize;
+            match i % 3 {
+                0 => rng.vec(len, 0, 10),
+                1 => rng.vec(len, -10, 10),
+                _ => rng.vec(len, -(1 << 40), 1 << 40),
+            }
+        });
+        if let Some(d) = find_disagreement(&all_algorithms(), inputs) {
+            panic!("algorithms disagree on {:?}: {:?}", d.height, d.results);
+        }
+    }
+
+    #[test]
+    fn reports_first_disagreement() {
+        struct Wrong;
+        impl TrapAlgorithm for Wrong {
+            fn name(&self) -> &'static str {
+                "wrong"
+            }
+            fn trap(&self, height: &[i64]) -> i64 {
+                height.len().saturating_sub(2) as i64
+            }
+        }
+
+        let algorithms: Vec<Box<dyn TrapAlgorithm>> = vec![Box::new(TwoPointer), Box::new(Wrong)];
+        let inputs = vec![vec![1, 1], vec![2, 1, 2], vec![1, 1, 1]];
+        assert_eq!(
+            find_disagreement(&algorithms, inputs),
+            Some(Disagreement {
+                height: vec![1, 1, 1],
+                results: vec![("two pointer", 0), ("wrong", 1)],
+            })
+        );
+    }
+}
@@ -0,0 +1,306 @@
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    max: i64,
+    sum: i64,
+    /// Sum of the prefix maxima of the right child, starting from the maximum
+    /// of the left child.
+    pre: i64,
+    /// Sum of the suffix maxima of the left child, starting from the maximum
+    /// of the right child.
+    suf: i64,
+}
+
+/// Trapped water over an elevation profile whose heights change.
+///
+/// The water over `i` is `min(prefix_max[i], suffix_max[i]) - height[i]`;
+/// left of the highest bar the minimum is the prefix maximum, right of it the
+/// suffix maximum. The segment tree (children of `id` at `2 * id + 1` and
+/// `2 * id + 2`) lets both sums of maxima be computed in O(log^2 n), so every
+/// operation is O(log^2 n).
+#[derive(Debug, Clone)]
+pub struct DynamicWater {
+    nodes: Vec<Node>,
+    size: usize,
+}
+
+impl DynamicWater {
+    pub fn new(height: &[i64]) -> Self {
+        let capacity = height.len().next_power_of_two() * 2 - 1;
+        let mut tree = Self {
+            nodes: vec![Node::default(); capacity],
+            size: height.len(),
+        };
+        if !height.is_empty() {
+            tree.build(0, 0, height.len(), height);
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn height(&self, i: usize) -> i64 {
+        assert!(i < self.size, "index out of bounds");
+        let (mut id, mut lo, mut hi) = (0, 0, self.size);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if i < mid {
+                (id, hi) = (Self::left_child(id), mid);
+            } else {
+                (id, lo) = (Self::right_child(id), mid);
+            }
+        }
+        self.nodes[id].max
+    }
+
+    pub fn set_height(&mut self, i: usize, h: i64) {
+        assert!(i < self.size, "index out of bounds");
+        self.update(0, 0, self.size, i, h);
+    }
+
+    pub fn total_water(&self) -> i64 {
+        if self.size == 0 {
+            return 0;
+        }
+        self.water_in(0, self.size - 1)
+    }
+
+    /// Water over the indices `l..=r` of the whole profile.
+    pub fn water_in(&self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r < self.size, "invalid range");
+        let peak = self.peak();
+        let mut water = -self.sum(0, 0, self.size, l, r + 1);
+        if l < peak {
+            let mut bound = if l == 0 {
+                i64::MIN
+            } else {
+                self.range_max(0, 0, self.size, 0, l)
+            };
+            water += self.prefix_maxima(0, 0, self.size, l, peak.min(r + 1), &mut bound);
+        }
+        if r >= peak {
+            let mut bound = if r + 1 == self.size {
+                i64::MIN
+            } else {
+                self.range_max(0, 0, self.size, r + 1, self.size)
+            };
+            water += self.suffix_maxima(0, 0, self.size, peak.max(l), r + 1, &mut bound);
+        }
+        water
+    }
+
+    fn left_child(id: usize) -> usize {
+        id * 2 + 1
+    }
+
+    fn right_child(id: usize) -> usize {
+        id * 2 + 2
+    }
+
+    /// Position of a highest bar.
+    fn peak(&self) -> usize {
+        let (mut id, mut lo, mut hi) = (0, 0, self.size);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if self.nodes[Self::left_child(id)].max == self.nodes[id].max {
+                (id, hi) = (Self::left_child(id), mid);
+            } else {
+                (id, lo) = (Self::right_child(id), mid);
+            }
+        }
+        lo
+    }
+
+    fn build(&mut self, id: usize, lo: usize, hi: usize, height: &[i64]) {
+        if hi - lo == 1 {
+            self.nodes[id] = Node {
+                max: height[lo],
+                sum: height[lo],
+                ..Node::default()
+            };
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(Self::left_child(id), lo, mid, height);
+        self.build(Self::right_child(id), mid, hi, height);
+        self.pull(id, lo, hi);
+    }
+
+    fn update(&mut self, id: usize, lo: usize, hi: usize, i: usize, h: i64) {
+        if hi - lo == 1 {
+            self.nodes[id].max = h;
+            self.nodes[id].sum = h;
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if i < mid {
+            self.update(Self::left_child(id), lo, mid, i, h);
+        } else {
+            self.update(Self::right_child(id), mid, hi, i, h);
+        }
+        self.pull(id, lo, hi);
+    }
+
+    fn pull(&mut self, id: usize, lo: usize, hi: usize) {
+        let mid = (lo + hi) / 2;
+        let (l, r) = (Self::left_child(id), Self::right_child(id));
+        self.nodes[id] = Node {
+            max: self.nodes[l].max.max(self.nodes[r].max),
+            sum: self.nodes[l].sum + self.nodes[r].sum,
+            pre: self.prefix_from(r, mid, hi, self.nodes[l].max),
+            suf: self.suffix_from(l, lo, mid, self.nodes[r].max),
+        };
+    }
+
+    /// Sum of the prefix maxima of the node, starting from `bound`.
+    fn prefix_from(&self, id: usize, lo: usize, hi: usize, bound: i64) -> i64 {
+        if bound >= self.nodes[id].max {
+            return bound * (hi - lo) as i64;
+        }
+        if hi - lo == 1 {
+            return self.nodes[id].max;
+        }
+        let mid = (lo + hi) / 2;
+        let l = Self::left_child(id);
+        if bound >= self.nodes[l].max {
+            bound * (mid - lo) as i64 + self.prefix_from(Self::right_child(id), mid, hi, bound)
+        } else {
+            self.prefix_from(l, lo, mid, bound) + self.nodes[id].pre
+        }
+    }
+
+    /// Sum of the suffix maxima of the node, starting from `bound`.
+    fn suffix_from(&self, id: usize, lo: usize, hi: usize, bound: i64) -> i64 {
+        if bound >= self.nodes[id].max {
+            return bound * (hi - lo) as i64;
+        }
+        if hi - lo == 1 {
+            return self.nodes[id].max;
+        }
+        let mid = (lo + hi) / 2;
+        let r = Self::right_child(id);
+        if bound >= self.nodes[r].max {
+            bound * (hi - mid) as i64 + self.suffix_from(Self::left_child(id), lo, mid, bound)
+        } else {
+            self.suffix_from(r, mid, hi, bound) + self.nodes[id].suf
+        }
+    }
+
+    /// Sum of the prefix maxima over `ql..qr`, starting from `bound`.
+    fn prefix_maxima(
+        &self,
+        id: usize,
+        lo: usize,
+        hi: usize,
+        ql: usize,
+        qr: usize,
+        bound: &mut i64,
+    ) -> i64 {
+        if qr <= lo || hi <= ql {
+            return 0;
+        }
+        if ql <= lo && hi <= qr {
+            let res = self.prefix_from(id, lo, hi, *bound);
+            *bound = (*bound).max(self.nodes[id].max);
+            return res;
+        }
+        let mid = (lo + hi) / 2;
+        self.prefix_maxima(Self::left_child(id), lo, mid, ql, qr, bound)
+            + self.prefix_maxima(Self::right_child(id), mid, hi, ql, qr, bound)
+    }
+
+    /// Sum of the suffix maxima over `ql..qr`, starting from `bound`.
+    fn suffix_maxima(
+        &self,
+        id: usize,
+        lo: usize,
+        hi: usize,
+        ql: usize,
+        qr: usize,
+        bound: &mut i64,
+    ) -> i64 {
+        if qr <= lo || hi <= ql {
+            return 0;
+        }
+        if ql <= lo && hi <= qr {
+            let res = self.suffix_from(id, lo, hi, *bound);
+            *bound = (*bound).max(self.nodes[id].max);
+            return res;
+        }
+        let mid = (lo + hi) / 2;
+        self.suffix_maxima(Self::right_child(id), mid, hi, ql, qr, bound)
+            + self.suffix_maxima(Self::left_child(id), lo, mid, ql, qr, bound)
+    }
+
+    fn range_max(&self, id: usize, lo: usize, hi: usize, ql: usize, qr: usize) -> i64 {
+        if qr <= lo || hi <= ql {
+            return i64::MIN;
+        }
+        if ql <= lo && hi <= qr {
+            return self.nodes[id].max;
+        }
+        let mid = (lo + hi) / 2;
+        self.range_max(Self::left_child(id), lo, mid, ql, qr)
+            .max(self.range_max(Self::right_child(id), mid, hi, ql, qr))
+    }
+
+    fn sum(&self, id: usize, lo: usize, hi: usize, ql: usize, qr: usize) -> i64 {
+        if qr <= lo || hi <= ql {
+            return 0;
+        }
+        if ql <= lo && hi <= qr {
+            return self.nodes[id].sum;
+        }
+        let mid = (lo + hi) / 2;
+        self.sum(Self::left_child(id), lo, mid, ql, qr)
+            + self.sum(Self::right_child(id), mid, hi, ql, qr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::WaterProfile;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example_with_updates() {
+        let mut water = DynamicWater::new(&[4, 2, 0, 3, 2, 5]);
+        assert_eq!(water.total_water(), 9);
+        assert_eq!(water.water_in(1, 2), 6);
+        water.set_height(5, 1);
+        assert_eq!(water.total_water(), 4);
+        water.set_height(0, 0);
+        assert_eq!(water.total_water(), 2);
+        assert_eq!(water.height(3), 3);
+        assert_eq!(DynamicWater::new(&[]).total_water(), 0);
+    }
+
+    #[test]
+    fn random_updates_against_profile() {
+        let mut rng = Rng::new(39);
+        for _ in 0..50 {
+            let len = rng.below(30) as usize + 1;
+            let mut height = rng.vec(len, 0, 15);
+            let mut water = DynamicWater::new(&height);
+            for _ in 0..30 {
+                let i = rng.below(len as u64) as usize;
+                height[i] = rng.range(0, 15);
+                water.set_height(i, height[i]);
+
+                let as_i32: Vec<i32> = height.iter().map(|h| *h as i32).collect();
+                let profile = WaterProfile::new(&as_i32);
+                assert_eq!(water.total_water(), profile.total());
+                let l = rng.below(len as u64) as usize;
+                let r = l + rng.below((len - l) as u64) as usize;
+                let expected: i64 = profile.water[l..=r].iter().sum();
+                assert_eq!(water.water_in(l, r), expected);
+            }
+        }
+    }
+}
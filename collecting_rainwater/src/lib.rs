@@ -0,0 +1,44 @@
+pub mod algorithms;
+pub mod dynamic;
+pub mod profile;
+pub mod range_water;
+pub mod trap_2d;
+
+use algorithms::{Leaders, TrapAlgorithm};
+
+pub struct Solution {}
+impl Solution {
+    /// Panics if the trapped water does not fit in an `i32`; see
+    /// [`Solution::trap_i64`].
+    pub fn trap(height: Vec<i32>) -> i32 {
+        i32::try_from(Self::trap_i64(&height)).expect("trapped water overflows i32")
+    }
+
+    pub fn trap_i64(height: &[i32]) -> i64 {
+        let height: Vec<i64> = height.iter().copied().map(i64::from).collect();
+        Leaders.trap(&height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide_basin() -> Vec<i32> {
+        let mut height = vec![0; 100_000];
+        height[0] = 100_000;
+        height[99_999] = 100_000;
+        height
+    }
+
+    #[test]
+    fn water_beyond_i32() {
+        assert_eq!(Solution::trap_i64(&wide_basin()), 9_999_800_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows i32")]
+    fn trap_panics_instead_of_truncating() {
+        Solution::trap(wide_basin());
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use leaders_array::leaders::{prefix_max, suffix_max};
+
+/// Water trapped over each index of an elevation profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaterProfile {
+    pub height: Vec<i32>,
+    /// Height of the water surface over each index, equal to `height[i]`
+    /// where no water is trapped.
+    pub level: Vec<i32>,
+    /// Depth of the water over each index, `level[i] - height[i]`, which
+    /// can exceed `i32::MAX`.
+    pub water: Vec<i64>,
+}
+
+impl WaterProfile {
+    /// Water over each index of `height`: the lower of the highest bars on
+    /// its left and on its right, itself included.
+    pub fn new(height: &[i32]) -> Self {
+        let level: Vec<i32> = prefix_max(height)
+            .into_iter()
+            .zip(suffix_max(height))
+            .map(|(l, r)| l.min(r))
+            .collect();
+        let water = level
+            .iter()
+            .zip(height)
+            .map(|(l, h)| i64::from(*l) - i64::from(*h))
+            .collect();
+
+        Self {
+            height: height.to_vec(),
+            level,
+            water,
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.water.iter().sum()
+    }
+
+    /// Plain text diagram with `#` for the terrain and `~` for the water,
+    /// one line per unit of height from the top.
+    ///
+    /// The diagram has `top x len` cells, `top` being the highest water
+    /// level, so it is only meant for small heights: around 10^9 it would
+    /// take gigabytes.
+    pub fn render(&self) -> String {
+        self.render_with("#", "~", " ")
+    }
+
+    /// Same as [`WaterProfile::render`], coloured with ANSI escape codes.
+    pub fn render_ansi(&self) -> String {
+        self.render_with("\x1b[33m#\x1b[0m", "\x1b[34m~\x1b[0m", " ")
+    }
+
+    fn render_with(&self, terrain: &str, water: &str, air: &str) -> String {
+        let top = self.level.iter().copied().max().unwrap_or(0);
+        let mut out = String::new();
+        for row in (1..=top).rev() {
+            for (h, l) in self.height.iter().zip(&self.level) {
+                let cell = if *h >= row {
+                    terrain
+                } else if *l >= row {
+                    water
+                } else {
+                    air
+                };
+                out.push_str(cell);
+            }
+            out.push('\n');
+        }
+        let _ = writeln!(out, "{}", "-".repeat(self.height.len()));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example() {
+        let profile = WaterProfile::new(&[4, 2, 0, 3, 2, 5]);
+        assert_eq!(profile.level, vec![4, 4, 4, 4, 4, 5]);
+        assert_eq!(profile.water, vec![0, 2, 4, 1, 2, 0]);
+        assert_eq!(profile.total(), 9);
+        assert_eq!(
+            profile.render(),
+            concat!("     #\n", "#~~~~#\n", "#~~#~#\n", "##~###\n", "##~###\n", "------\n")
+        );
+    }
+
+    #[test]
+    fn random_totals_match_trap() {
+        let mut rng = Rng::new(38);
+        for _ in 0..200 {
+            let len = rng.below(20) as usize;
+            let height: Vec<i32> = rng.vec(len, 0, 10).iter().map(|h| *h as i32).collect();
+            let profile = WaterProfile::new(&height);
+            assert_eq!(profile.total(), Solution::trap(height) as i64);
+            assert!(profile.water.iter().all(|w| *w >= 0));
+        }
+    }
+
+    #[test]
+    fn extreme_heights() {
+        let profile = WaterProfile::new(&[i32::MAX, i32::MIN, i32::MAX]);
+        assert_eq!(profile.water, vec![0, u32::MAX as i64, 0]);
+        assert_eq!(profile.total(), u32::MAX as i64);
+    }
+}
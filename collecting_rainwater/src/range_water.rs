@@ -0,0 +1,157 @@
+/// Answers "water trapped by `height[l..=r]` alone" in O(1) after
+/// O(n log n) preprocessing.
+///
+/// With `p1` and `p2` the leftmost and rightmost highest bars of the range,
+/// the water left of `p1` only depends on the prefix maxima from `l`, the one
+/// right of `p2` on the suffix maxima from `r`, and between them it rises to
+/// the highest bar. `pre[i]` is the sum of the prefix maxima of `height[i..]`
+/// and `suf[i + 1]` the sum of the suffix maxima of `height[..=i]`; both are
+/// built along next/previous greater element chains, which go through `p1`
+/// and `p2` respectively.
+#[derive(Debug, Clone)]
+pub struct RangeWater {
+    height: Vec<i64>,
+    sum: Vec<i64>,
+    pre: Vec<i64>,
+    suf: Vec<i64>,
+    /// `leftmost[k][i]` is the leftmost highest bar of `height[i..i + 2^k]`.
+    leftmost: Vec<Vec<usize>>,
+    /// `rightmost[k][i]` is the rightmost highest bar of `height[i..i + 2^k]`.
+    rightmost: Vec<Vec<usize>>,
+}
+
+impl RangeWater {
+    pub fn new(height: &[i64]) -> Self {
+        let n = height.len();
+        let mut sum = vec![0; n + 1];
+        for i in 0..n {
+            sum[i + 1] = sum[i] + height[i];
+        }
+
+        let mut pre = vec![0; n + 1];
+        let mut stack: Vec<usize> = Vec::new();
+        for i in (0..n).rev() {
+            while stack.last().is_some_and(|j| height[*j] <= height[i]) {
+                stack.pop();
+            }
+            let next = stack.last().copied().unwrap_or(n);
+            pre[i] = height[i] * (next - i) as i64 + pre[next];
+            stack.push(i);
+        }
+
+        let mut suf = vec![0; n + 1];
+        stack.clear();
+        for i in 0..n {
+            while stack.last().is_some_and(|j| height[*j] <= height[i]) {
+                stack.pop();
+            }
+            let prev = stack.last().map_or(0, |j| j + 1);
+            suf[i + 1] = height[i] * (i + 1 - prev) as i64 + suf[prev];
+            stack.push(i);
+        }
+
+        let mut leftmost = vec![(0..n).collect::<Vec<usize>>()];
+        let mut rightmost = leftmost.clone();
+        let mut width = 1;
+        while 2 * width <= n {
+            let (l, r) = (leftmost.last().unwrap(), rightmost.last().unwrap());
+            let next_l = (0..=n - 2 * width)
+                .map(|i| {
+                    let (a, b) = (l[i], l[i + width]);
+                    if height[b] > height[a] {
+                        b
+                    } else {
+                        a
+                    }
+                })
+                .collect();
+            let next_r = (0..=n - 2 * width)
+                .map(|i| {
+                    let (a, b) = (r[i], r[i + width]);
+                    if height[a] > height[b] {
+                        a
+                    } else {
+                        b
+                    }
+                })
+                .collect();
+            leftmost.push(next_l);
+            rightmost.push(next_r);
+            width *= 2;
+        }
+
+        Self {
+            height: height.to_vec(),
+            sum,
+            pre,
+            suf,
+            leftmost,
+            rightmost,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.height.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.height.is_empty()
+    }
+
+    /// Water trapped if only `height[l..=r]` existed.
+    pub fn query(&self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r < self.len(), "invalid range");
+        let k = (r - l + 1).ilog2() as usize;
+        let width = 1 << k;
+        let (a, b) = (self.leftmost[k][l], self.leftmost[k][r + 1 - width]);
+        let p1 = if self.height[b] > self.height[a] {
+            b
+        } else {
+            a
+        };
+        let (a, b) = (self.rightmost[k][l], self.rightmost[k][r + 1 - width]);
+        let p2 = if self.height[a] > self.height[b] {
+            a
+        } else {
+            b
+        };
+
+        let left = (self.pre[l] - self.pre[p1]) - (self.sum[p1] - self.sum[l]);
+        let middle = self.height[p1] * (p2 + 1 - p1) as i64 - (self.sum[p2 + 1] - self.sum[p1]);
+        let right = (self.suf[r + 1] - self.suf[p2 + 1]) - (self.sum[r + 1] - self.sum[p2 + 1]);
+        left + middle + right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example_subranges() {
+        let water = RangeWater::new(&[4, 2, 0, 3, 2, 5]);
+        assert_eq!(water.query(0, 5), 9);
+        assert_eq!(water.query(0, 3), 4);
+        assert_eq!(water.query(1, 4), 2);
+        assert_eq!(water.query(2, 2), 0);
+    }
+
+    #[test]
+    fn random_against_trap_on_subslice() {
+        let mut rng = Rng::new(40);
+        for _ in 0..50 {
+            let len = rng.below(40) as usize + 1;
+            let height = rng.vec(len, 0, 12);
+            let water = RangeWater::new(&height);
+            let as_i32: Vec<i32> = height.iter().map(|h| *h as i32).collect();
+            for l in 0..len {
+                for r in l..len {
+                    let expected = Solution::trap(as_i32[l..=r].to_vec()) as i64;
+                    assert_eq!(water.query(l, r), expected, "{:?}", &height[l..=r]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::Solution;
+
+/// Water trapped on a height grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trapped2d {
+    pub total: i64,
+    /// Height of the water surface over each cell, equal to the cell height
+    /// where no water is trapped.
+    pub level: Vec<Vec<i32>>,
+}
+
+/// Water trapped on the `n x m` grid `heights`, where water flows off the
+/// border, in O(nm log(nm)).
+///
+/// The border cells go in a min-heap keyed by their water level. Popping the
+/// lowest one, each unvisited neighbour gets the level `max(own height,
+/// popped level)`, since water can leave it through the popped cell and no
+/// lower escape exists.
+pub fn trap_2d(heights: &[Vec<i32>]) -> Trapped2d {
+    let n = heights.len();
+    let m = heights.first().map_or(0, Vec::len);
+    assert!(
+        heights.iter().all(|row| row.len() == m),
+        "grid rows must have the same length"
+    );
+    let mut level = heights.to_vec();
+    let mut visited = vec![vec![false; m]; n];
+    let mut heap = BinaryHeap::new();
+
+    for i in 0..n {
+        for j in 0..m {
+            if i == 0 || j == 0 || i == n - 1 || j == m - 1 {
+                visited[i][j] = true;
+                heap.push(Reverse((heights[i][j], i, j)));
+            }
+        }
+    }
+
+    let mut total = 0;
+    while let Some(Reverse((h, i, j))) = heap.pop() {
+        let neighbours = [
+            (i.wrapping_sub(1), j),
+            (i + 1, j),
+            (i, j.wrapping_sub(1)),
+            (i, j + 1),
+        ];
+        for (x, y) in neighbours {
+            if x >= n || y >= m || visited[x][y] {
+                continue;
+            }
+            visited[x][y] = true;
+            level[x][y] = h.max(heights[x][y]);
+            total += i64::from(level[x][y]) - i64::from(heights[x][y]);
+            heap.push(Reverse((level[x][y], x, y)));
+        }
+    }
+
+    Trapped2d { total, level }
+}
+
+impl Solution {
+    /// Panics if the trapped water does not fit in an `i32`; [`trap_2d`]
+    /// reports it as an `i64`.
+    pub fn trap_rain_water(height_map: Vec<Vec<i32>>) -> i32 {
+        i32::try_from(trap_2d(&height_map).total).expect("trapped water overflows i32")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    /// Surrounds the profile with walls above and below, so water can only
+    /// leave from its two ends as in the 1D problem.
+    fn walled(height: &[i32]) -> Vec<Vec<i32>> {
+        let wall = vec![height.iter().copied().max().unwrap_or(0); height.len()];
+        vec![wall.clone(), height.to_vec(), wall]
+    }
+
+    #[test]
+    fn leetcode_examples() {
+        let map = vec![
+            vec![1, 4, 3, 1, 3, 2],
+            vec![3, 2, 1, 3, 2, 4],
+            vec![2, 3, 3, 2, 3, 1],
+        ];
+        assert_eq!(Solution::trap_rain_water(map), 4);
+
+        let map = vec![
+            vec![3, 3, 3, 3, 3],
+            vec![3, 2, 2, 2, 3],
+            vec![3, 2, 1, 2, 3],
+            vec![3, 2, 2, 2, 3],
+            vec![3, 3, 3, 3, 3],
+        ];
+        let trapped = trap_2d(&map);
+        assert_eq!(trapped.total, 10);
+        assert!(trapped.level[1..4].iter().all(|row| row[1..4] == [3, 3, 3]));
+        assert_eq!(trap_2d(&[]).total, 0);
+    }
+
+    #[test]
+    fn extreme_heights() {
+        let (hi, lo) = (i32::MAX, i32::MIN);
+        let map = vec![vec![hi, hi, hi], vec![hi, lo, hi], vec![hi, hi, hi]];
+        let trapped = trap_2d(&map);
+        assert_eq!(trapped.total, u32::MAX as i64);
+        assert_eq!(trapped.level[1][1], hi);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows i32")]
+    fn trap_rain_water_panics_instead_of_truncating() {
+        let (hi, lo) = (i32::MAX, i32::MIN);
+        Solution::trap_rain_water(vec![vec![hi, hi, hi], vec![hi, lo, hi], vec![hi, hi, hi]]);
+    }
+
+    #[test]
+    fn single_rows_trap_nothing() {
+        let trapped = trap_2d(&[vec![4, 2, 0, 3, 2, 5]]);
+        assert_eq!(trapped.total, 0);
+        assert_eq!(trapped.level, vec![vec![4, 2, 0, 3, 2, 5]]);
+    }
+
+    #[test]
+    fn walled_rows_reduce_to_1d() {
+        assert_eq!(trap_2d(&walled(&[4, 2, 0, 3, 2, 5])).total, 9);
+        let mut rng = Rng::new(37);
+        for _ in 0..200 {
+            let len = rng.below(20) as usize + 1;
+            let height: Vec<i32> = rng.vec(len, 0, 10).iter().map(|h| *h as i32).collect();
+            let trapped = trap_2d(&walled(&height));
+            assert_eq!(trapped.total, Solution::trap(height) as i64);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// Configurable leader scan: an element is a leader if it is greater than
+/// every element after it (or before it, scanning left to right).
+///
+/// `strict` decides whether ties count as dominated (`>`) or not (`>=`).
+/// Results are always reported in the original order of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderScan {
+    strict: bool,
+    left_to_right: bool,
+}
+
+impl LeaderScan {
+    /// Strict leaders compared with the elements on their right.
+    pub fn new() -> Self {
+        Self {
+            strict: true,
+            left_to_right: false,
+        }
+    }
+
+    /// Leaders only need to be at least as large as the others.
+    pub fn non_strict(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    /// Compares each element with the ones on its left.
+    pub fn left_to_right(mut self) -> Self {
+        self.left_to_right = true;
+        self
+    }
+
+    /// Compares each element with the ones on its right.
+    pub fn right_to_left(mut self) -> Self {
+        self.left_to_right = false;
+        self
+    }
+
+    pub fn indices<T: Ord>(&self, a: &[T]) -> Vec<usize> {
+        let mut leaders = Vec::new();
+        let mut max: Option<&T> = None;
+        let mut visit = |i: usize| {
+            let is_leader = match max {
+                None => true,
+                Some(m) if self.strict => a[i] > *m,
+                Some(m) => a[i] >= *m,
+            };
+            if is_leader {
+                max = Some(&a[i]);
+                leaders.push(i);
+            }
+        };
+        if self.left_to_right {
+            (0..a.len()).for_each(&mut visit);
+        } else {
+            (0..a.len()).rev().for_each(&mut visit);
+            leaders.reverse();
+        }
+        leaders
+    }
+
+    pub fn values<T: Ord + Clone>(&self, a: &[T]) -> Vec<T> {
+        self.indices(a).into_iter().map(|i| a[i].clone()).collect()
+    }
+
+    /// `(index, value)` of every leader.
+    pub fn pairs<T: Ord + Clone>(&self, a: &[T]) -> Vec<(usize, T)> {
+        self.indices(a)
+            .into_iter()
+            .map(|i| (i, a[i].clone()))
+            .collect()
+    }
+}
+
+impl Default for LeaderScan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `result[i]` is the maximum of `a[..=i]`.
+pub fn prefix_max<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    let mut result: Vec<T> = Vec::with_capacity(a.len());
+    for el in a {
+        let max = match result.last() {
+            Some(m) if m > el => m.clone(),
+            _ => el.clone(),
+        };
+        result.push(max);
+    }
+    result
+}
+
+/// `result[i]` is the maximum of `a[i..]`.
+pub fn suffix_max<T: Ord + Clone>(a: &[T]) -> Vec<T> {
+    let mut result: Vec<T> = Vec::with_capacity(a.len());
+    for el in a.iter().rev() {
+        let max = match result.last() {
+            Some(m) if m > el => m.clone(),
+            _ => el.clone(),
+        };
+        result.push(max);
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strictness_and_direction() {
+        let a = [16, 17, 4, 3, 5, 2, 5];
+        assert_eq!(LeaderScan::new().values(&a), vec![17, 5]);
+        assert_eq!(LeaderScan::new().non_strict().indices(&a), vec![1, 4, 6]);
+        assert_eq!(
+            LeaderScan::new().left_to_right().pairs(&a),
+            vec![(0, 16), (1, 17)]
+        );
+        assert_eq!(
+            LeaderScan::new()
+                .left_to_right()
+                .non_strict()
+                .values(&[2, 2, 1, 3]),
+            vec![2, 2, 3]
+        );
+        assert!(LeaderScan::new().values::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn leaders_are_where_the_maxima_change() {
+        let a = [3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(prefix_max(&a), vec![3, 3, 4, 4, 5, 9, 9, 9]);
+        assert_eq!(suffix_max(&a), vec![9, 9, 9, 9, 9, 9, 6, 6]);
+
+        let suffix = suffix_max(&a);
+        let expected: Vec<usize> = (0..a.len())
+            .filter(|i| *i + 1 == a.len() || a[*i] > suffix[*i + 1])
+            .collect();
+        assert_eq!(LeaderScan::new().indices(&a), expected);
+    }
+}
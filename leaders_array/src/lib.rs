@@ -0,0 +1,13 @@
+pub mod leaders;
+pub mod monotonic_stack;
+pub mod online;
+pub mod pareto;
+
+use leaders::LeaderScan;
+
+/// Elements greater than all the elements to their right, from the last one.
+pub fn leader(a: &[i32]) -> Vec<i32> {
+    let mut leaders = LeaderScan::new().values(a);
+    leaders.reverse();
+    leaders
+}
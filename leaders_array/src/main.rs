@@ -1,5 +1,7 @@
 use std::io;
 
+use leaders_array::leader;
+
 fn main() {
     let mut n = String::new();
     println!("Enter the number of elements in the array: ");
@@ -17,16 +19,3 @@ fn main() {
 
     println!("Input: {:?} Leaders: {:?}", test_input, leader(&test_input));
 }
-
-fn leader(a: &[i32]) -> Vec<i32> {
-    let mut max = a[a.len() - 1];
-    let mut leaders: Vec<i32> = Vec::new();
-    leaders.push(max);
-    for el in a.iter().rev() {
-        if *el > max {
-            max = *el;
-            leaders.push(max);
-        }
-    }
-    return leaders;
-}
@@ -0,0 +1,204 @@
+/// For each `i`, the closest index `j` scanned after it (in `order`) with
+/// `dominates(a[j], a[i])`, found with a stack of still unanswered indices.
+fn closest<T, I, F>(a: &[T], order: I, dominates: F) -> Vec<Option<usize>>
+where
+    I: Iterator<Item = usize>,
+    F: Fn(&T, &T) -> bool,
+{
+    let mut result = vec![None; a.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    for j in order {
+        while let Some(&i) = stack.last() {
+            if !dominates(&a[j], &a[i]) {
+                break;
+            }
+            result[i] = Some(j);
+            stack.pop();
+        }
+        stack.push(j);
+    }
+    result
+}
+
+/// Index of the first later element strictly greater than each element.
+pub fn next_greater<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    closest(a, 0..a.len(), |x, y| x > y)
+}
+
+/// Index of the last earlier element strictly greater than each element.
+pub fn prev_greater<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    closest(a, (0..a.len()).rev(), |x, y| x > y)
+}
+
+/// Index of the first later element strictly smaller than each element.
+pub fn next_smaller<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    closest(a, 0..a.len(), |x, y| x < y)
+}
+
+/// Index of the last earlier element strictly smaller than each element.
+pub fn prev_smaller<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    closest(a, (0..a.len()).rev(), |x, y| x < y)
+}
+
+/// For each day, the number of consecutive days ending there with a price
+/// not above that day's price.
+pub fn stock_span<T: Ord>(prices: &[T]) -> Vec<usize> {
+    prev_greater(prices)
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| p.map_or(i + 1, |p| i - p))
+        .collect()
+}
+
+/// Largest rectangle in the histogram `heights`, as `(area, first bar, last
+/// bar)`, the leftmost on ties. `None` if there are no bars.
+///
+/// Heights are any unsigned type that widens to `u64`; the area is computed
+/// in `u128`, where it cannot overflow.
+pub fn largest_rectangle<T: Copy + Ord + Into<u64>>(heights: &[T]) -> Option<(u128, usize, usize)> {
+    let prev = prev_smaller(heights);
+    let next = next_smaller(heights);
+    let mut best: Option<(u128, usize, usize)> = None;
+    for (i, h) in heights.iter().enumerate() {
+        let l = prev[i].map_or(0, |p| p + 1);
+        let r = next[i].unwrap_or(heights.len()) - 1;
+        let area = u128::from((*h).into()) * (r + 1 - l) as u128;
+        if best.is_none_or(|(b, bl, _)| area > b || (area == b && l < bl)) {
+            best = Some((area, l, r));
+        }
+    }
+    best
+}
+
+/// Area of the largest rectangle made only of `true` cells.
+///
+/// Only whether a cell is filled matters, so the cells are plain `bool`s; the
+/// histogram handed to [`largest_rectangle`] counts filled cells per column.
+pub fn maximal_rectangle(matrix: &[Vec<bool>]) -> u64 {
+    let width = matrix.first().map_or(0, Vec::len);
+    let mut heights = vec![0u64; width];
+    let mut best = 0;
+    for row in matrix {
+        assert_eq!(row.len(), width, "matrix rows must have the same length");
+        for (h, cell) in heights.iter_mut().zip(row) {
+            *h = if *cell { *h + 1 } else { 0 };
+        }
+        if let Some((area, _, _)) = largest_rectangle(&heights) {
+            best = best.max(u64::try_from(area).expect("area is at most the number of cells"));
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn next_and_previous_elements() {
+        let a = [2, 1, 2, 4, 3];
+        assert_eq!(
+            next_greater(&a),
+            vec![Some(3), Some(2), Some(3), None, None]
+        );
+        assert_eq!(prev_greater(&a), vec![None, Some(0), None, None, Some(3)]);
+        assert_eq!(next_smaller(&a), vec![Some(1), None, None, Some(4), None]);
+        assert_eq!(
+            prev_smaller(&a),
+            vec![None, None, Some(1), Some(2), Some(2)]
+        );
+        assert!(next_greater::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn stock_span_example() {
+        let prices = [100, 80, 60, 70, 60, 75, 85];
+        assert_eq!(stock_span(&prices), vec![1, 1, 1, 2, 1, 4, 6]);
+    }
+
+    #[test]
+    fn rectangles() {
+        assert_eq!(largest_rectangle(&[2u64, 1, 5, 6, 2, 3]), Some((10, 2, 3)));
+        assert_eq!(largest_rectangle(&[2u8, 4]), Some((4, 0, 1)));
+        assert_eq!(
+            largest_rectangle(&[u32::MAX, u32::MAX]),
+            Some((2 * u32::MAX as u128, 0, 1))
+        );
+        assert_eq!(
+            largest_rectangle(&[u64::MAX, u64::MAX]),
+            Some((2 * u64::MAX as u128, 0, 1))
+        );
+        assert_eq!(largest_rectangle::<u64>(&[]), None);
+
+        let matrix: Vec<Vec<bool>> = ["10100", "10111", "11111", "10010"]
+            .iter()
+            .map(|row| row.chars().map(|c| c == '1').collect())
+            .collect();
+        assert_eq!(maximal_rectangle(&matrix), 6);
+        assert_eq!(maximal_rectangle(&[]), 0);
+    }
+
+    #[test]
+    fn leaders_have_no_next_greater_or_equal() {
+        let a = [16, 17, 4, 3, 5, 2];
+        let next = next_greater(&a);
+        let leaders: Vec<usize> = (0..a.len())
+            .filter(|i| next[*i].is_none())
+            .filter(|i| !a[i + 1..].contains(&a[*i]))
+            .collect();
+        assert_eq!(leaders, crate::leaders::LeaderScan::new().indices(&a));
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(43);
+        for _ in 0..300 {
+            let len = rng.below(12) as usize;
+            // Few distinct values, so ties are frequent.
+            let a: Vec<u64> = rng.vec(len, 0, 4).iter().map(|x| *x as u64).collect();
+            let n = a.len();
+            let expected = |greater: bool, later: bool| -> Vec<Option<usize>> {
+                (0..n)
+                    .map(|i| {
+                        let beats = |j: &usize| if greater { a[*j] > a[i] } else { a[*j] < a[i] };
+                        if later {
+                            (i + 1..n).find(beats)
+                        } else {
+                            (0..i).rev().find(beats)
+                        }
+                    })
+                    .collect()
+            };
+            assert_eq!(next_greater(&a), expected(true, true), "{a:?}");
+            assert_eq!(prev_greater(&a), expected(true, false), "{a:?}");
+            assert_eq!(next_smaller(&a), expected(false, true), "{a:?}");
+            assert_eq!(prev_smaller(&a), expected(false, false), "{a:?}");
+
+            let span: Vec<usize> = (0..n)
+                .map(|i| (0..=i).rev().take_while(|j| a[*j] <= a[i]).count())
+                .collect();
+            assert_eq!(stock_span(&a), span, "{a:?}");
+
+            let mut best: Option<(u128, usize, usize)> = None;
+            for l in 0..n {
+                for r in l..n {
+                    let area = *a[l..=r].iter().min().unwrap() as u128 * (r + 1 - l) as u128;
+                    if best.is_none_or(|(b, bl, _)| area > b || (area == b && l < bl)) {
+                        best = Some((area, l, r));
+                    }
+                }
+            }
+            let got = largest_rectangle(&a);
+            assert_eq!(
+                got.map(|(area, l, _)| (area, l)),
+                best.map(|(area, l, _)| (area, l)),
+                "{a:?}"
+            );
+            if let Some((area, l, r)) = got {
+                let min = *a[l..=r].iter().min().unwrap() as u128;
+                assert_eq!(area, min * (r + 1 - l) as u128);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+#[derive(Debug, Clone)]
+struct Node<T> {
+    max: Option<T>,
+    /// Leaders of the node on its own.
+    count: usize,
+    /// Leaders of the left child above the maximum of the right child.
+    left_count: usize,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            max: None,
+            count: 0,
+            left_count: 0,
+        }
+    }
+}
+
+/// Strict leaders (greater than every element on their right) of a sequence
+/// that grows at the end and changes at arbitrary positions.
+///
+/// The segment tree (children of `id` at `2 * id + 1` and `2 * id + 2`) keeps
+/// the maximum of each node and its number of leaders. Counting the leaders
+/// of a node above a bound walks down a single path, so updates are
+/// O(log^2 n), [`OnlineLeaders::count`] is O(1) and listing `k` leaders is
+/// O(k log n). Appends double the capacity when full, amortized O(log^2 n).
+#[derive(Debug, Clone)]
+pub struct OnlineLeaders<T> {
+    nodes: Vec<Node<T>>,
+    values: Vec<T>,
+    capacity: usize,
+}
+
+impl<T: Ord + Clone> OnlineLeaders<T> {
+    pub fn new() -> Self {
+        Self::with_values(Vec::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> &T {
+        &self.values[i]
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.values.len() == self.capacity {
+            let mut values = std::mem::take(&mut self.values);
+            values.push(value);
+            *self = Self::with_values(values);
+        } else {
+            self.values.push(value);
+            self.update(0, 0, self.capacity, self.values.len() - 1);
+        }
+    }
+
+    pub fn set(&mut self, i: usize, value: T) {
+        assert!(i < self.len(), "index out of bounds");
+        self.values[i] = value;
+        self.update(0, 0, self.capacity, i);
+    }
+
+    /// Number of leaders.
+    pub fn count(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    /// Positions of the leaders, in increasing order.
+    pub fn indices(&self) -> Vec<usize> {
+        let mut leaders = Vec::new();
+        self.collect(0, 0, self.capacity, None, &mut leaders);
+        leaders
+    }
+
+    /// Values of the leaders, in the order they appear.
+    pub fn values(&self) -> Vec<T> {
+        self.indices()
+            .into_iter()
+            .map(|i| self.values[i].clone())
+            .collect()
+    }
+
+    fn with_values(values: Vec<T>) -> Self {
+        let capacity = values.len().next_power_of_two();
+        let mut leaders = Self {
+            nodes: vec![Node::default(); capacity * 2 - 1],
+            values,
+            capacity,
+        };
+        leaders.build(0, 0, capacity);
+        leaders
+    }
+
+    fn left_child(id: usize) -> usize {
+        id * 2 + 1
+    }
+
+    fn right_child(id: usize) -> usize {
+        id * 2 + 2
+    }
+
+    fn build(&mut self, id: usize, lo: usize, hi: usize) {
+        if hi - lo == 1 {
+            self.set_leaf(id, lo);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(Self::left_child(id), lo, mid);
+        self.build(Self::right_child(id), mid, hi);
+        self.pull(id, lo, mid);
+    }
+
+    fn update(&mut self, id: usize, lo: usize, hi: usize, i: usize) {
+        if hi - lo == 1 {
+            self.set_leaf(id, lo);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if i < mid {
+            self.update(Self::left_child(id), lo, mid, i);
+        } else {
+            self.update(Self::right_child(id), mid, hi, i);
+        }
+        self.pull(id, lo, mid);
+    }
+
+    fn set_leaf(&mut self, id: usize, i: usize) {
+        let max = self.values.get(i).cloned();
+        self.nodes[id] = Node {
+            count: max.is_some() as usize,
+            max,
+            left_count: 0,
+        };
+    }
+
+    fn pull(&mut self, id: usize, lo: usize, mid: usize) {
+        let (l, r) = (Self::left_child(id), Self::right_child(id));
+        let left_count = self.count_above(l, lo, mid, self.nodes[r].max.as_ref());
+        self.nodes[id] = Node {
+            max: self.nodes[l].max.clone().max(self.nodes[r].max.clone()),
+            count: left_count + self.nodes[r].count,
+            left_count,
+        };
+    }
+
+    /// Leaders of the node greater than `bound`, which stands for the
+    /// elements on its right.
+    fn count_above(&self, id: usize, lo: usize, hi: usize, bound: Option<&T>) -> usize {
+        if self.nodes[id].max.as_ref() <= bound {
+            return 0;
+        }
+        if hi - lo == 1 {
+            return 1;
+        }
+        let mid = (lo + hi) / 2;
+        let r = Self::right_child(id);
+        if self.nodes[r].max.as_ref() > bound {
+            self.count_above(r, mid, hi, bound) + self.nodes[id].left_count
+        } else {
+            self.count_above(Self::left_child(id), lo, mid, bound)
+        }
+    }
+
+    fn collect(&self, id: usize, lo: usize, hi: usize, bound: Option<&T>, out: &mut Vec<usize>) {
+        if self.nodes[id].max.as_ref() <= bound {
+            return;
+        }
+        if hi - lo == 1 {
+            out.push(lo);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let r = Self::right_child(id);
+        let right_max = self.nodes[r].max.as_ref();
+        self.collect(Self::left_child(id), lo, mid, bound.max(right_max), out);
+        self.collect(r, mid, hi, bound, out);
+    }
+}
+
+impl<T: Ord + Clone> Default for OnlineLeaders<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for OnlineLeaders<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::with_values(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leaders::LeaderScan;
+    use test_rng::Rng;
+
+    #[test]
+    fn appends_and_updates() {
+        let mut leaders: OnlineLeaders<i32> = [16, 17, 4, 3, 5, 2].into_iter().collect();
+        assert_eq!(leaders.count(), 3);
+        assert_eq!(leaders.values(), vec![17, 5, 2]);
+        leaders.push(6);
+        assert_eq!(leaders.values(), vec![17, 6]);
+        leaders.set(1, 1);
+        assert_eq!(leaders.indices(), vec![0, 6]);
+        leaders.set(6, 0);
+        assert_eq!(leaders.values(), vec![16, 5, 2, 0]);
+        assert_eq!(*leaders.get(4), 5);
+        assert_eq!(OnlineLeaders::<i32>::new().count(), 0);
+    }
+
+    #[test]
+    fn random_operations_against_scan() {
+        let mut rng = Rng::new(45);
+        for _ in 0..50 {
+            let mut leaders = OnlineLeaders::new();
+            let mut values: Vec<u64> = Vec::new();
+            for _ in 0..60 {
+                if values.is_empty() || rng.below(3) == 0 {
+                    let value = rng.below(10);
+                    values.push(value);
+                    leaders.push(value);
+                } else {
+                    let i = rng.below(values.len() as u64) as usize;
+                    values[i] = rng.below(10);
+                    leaders.set(i, values[i]);
+                }
+                let expected = LeaderScan::new().indices(&values);
+                assert_eq!(leaders.count(), expected.len());
+                assert_eq!(leaders.indices(), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BinaryHeap;
+
+use crate::leaders::LeaderScan;
+
+/// Indices of the points not dominated by any other, sorted by cost, where
+/// `(cost, quality)` dominates another point if it is no more expensive and
+/// no worse, and differs from it. Of identical points only the first is
+/// reported. O(n log n).
+///
+/// Sorted by cost (best quality first on ties), the frontier is made of the
+/// strict left-to-right leaders of the qualities.
+pub fn pareto_frontier<C: Ord, Q: Ord + Clone>(points: &[(C, Q)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| {
+        let (a, b) = (&points[*a], &points[*b]);
+        a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1))
+    });
+    let quality: Vec<Q> = order.iter().map(|i| points[*i].1.clone()).collect();
+    LeaderScan::new()
+        .left_to_right()
+        .indices(&quality)
+        .into_iter()
+        .map(|i| order[i])
+        .collect()
+}
+
+/// Outline of the buildings `(left, right, height)`, as the points `(x, h)`
+/// where its height changes to `h`. O(n log n).
+///
+/// Sweeping the building edges left to right, a max-heap holds the buildings
+/// covering the sweep line, lazily dropping the ones already ended; a key
+/// point is emitted whenever the running maximum changes. Unlike the running
+/// maximum of a [`LeaderScan`], this one has to forget buildings once they
+/// end and can go down, hence the heap instead of a leader scan.
+pub fn skyline(buildings: &[(i64, i64, i64)]) -> Vec<(i64, i64)> {
+    let mut xs: Vec<i64> = buildings.iter().flat_map(|b| [b.0, b.1]).collect();
+    xs.sort_unstable();
+    xs.dedup();
+    let mut order: Vec<usize> = (0..buildings.len()).collect();
+    order.sort_by_key(|i| buildings[*i].0);
+
+    let mut result: Vec<(i64, i64)> = Vec::new();
+    let mut heap: BinaryHeap<(i64, i64)> = BinaryHeap::new();
+    let mut next = 0;
+    for x in xs {
+        while next < order.len() && buildings[order[next]].0 <= x {
+            let (_, right, height) = buildings[order[next]];
+            heap.push((height, right));
+            next += 1;
+        }
+        while heap.peek().is_some_and(|(_, right)| *right <= x) {
+            heap.pop();
+        }
+        let height = heap.peek().map_or(0, |(h, _)| *h);
+        if result.last().is_none_or(|(_, h)| *h != height) {
+            result.push((x, height));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn frontier_of_cost_quality_points() {
+        let points = [(5, 3), (1, 1), (3, 4), (2, 1), (4, 4), (3, 4), (6, 9)];
+        assert_eq!(pareto_frontier(&points), vec![1, 2, 6]);
+        assert!(pareto_frontier::<i32, i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn frontier_against_brute_force() {
+        let mut rng = Rng::new(44);
+        for _ in 0..200 {
+            let costs = rng.vec(12, 0, 8);
+            let points: Vec<(i64, i64)> = costs.into_iter().zip(rng.vec(12, 0, 8)).collect();
+            let mut expected: Vec<(i64, i64)> = points
+                .iter()
+                .filter(|p| !points.iter().any(|q| q.0 <= p.0 && q.1 >= p.1 && q != *p))
+                .copied()
+                .collect();
+            expected.sort();
+            expected.dedup();
+            let got: Vec<(i64, i64)> = pareto_frontier(&points)
+                .iter()
+                .map(|i| points[*i])
+                .collect();
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn leetcode_skylines() {
+        let buildings = [
+            (2, 9, 10),
+            (3, 7, 15),
+            (5, 12, 12),
+            (15, 20, 10),
+            (19, 24, 8),
+        ];
+        assert_eq!(
+            skyline(&buildings),
+            vec![
+                (2, 10),
+                (3, 15),
+                (7, 12),
+                (12, 0),
+                (15, 10),
+                (20, 8),
+                (24, 0)
+            ]
+        );
+        assert_eq!(skyline(&[(0, 2, 3), (2, 5, 3)]), vec![(0, 3), (5, 0)]);
+        assert!(skyline(&[]).is_empty());
+    }
+
+    #[test]
+    fn skyline_against_brute_force() {
+        let mut rng = Rng::new(144);
+        for _ in 0..200 {
+            let buildings: Vec<(i64, i64, i64)> = (0..rng.below(6))
+                .map(|_| {
+                    let left = rng.range(0, 10);
+                    (left, left + rng.range(1, 6), rng.range(1, 5))
+                })
+                .collect();
+            let height_at = |x: i64| {
+                buildings
+                    .iter()
+                    .filter(|b| b.0 <= x && x < b.1)
+                    .map(|b| b.2)
+                    .max()
+                    .unwrap_or(0)
+            };
+            let mut expected: Vec<(i64, i64)> = Vec::new();
+            for x in 0..16 {
+                let h = height_at(x);
+                let changed = expected.last().map_or(h != 0, |(_, last)| *last != h);
+                if changed {
+                    expected.push((x, h));
+                }
+            }
+            assert_eq!(skyline(&buildings), expected, "{buildings:?}");
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::num::Num;
+
+/// Subarray `start..end` of the input and its sum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Subarray<T> {
+    pub sum: T,
+    pub start: usize,
+    /// One past the last element, so `start == end` for the empty subarray.
+    pub end: usize,
+}
+
+impl<T> Subarray<T> {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Which of several subarrays with the maximum sum is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Smallest start, then smallest end.
+    #[default]
+    Leftmost,
+    /// Shortest, then leftmost.
+    Shortest,
+    /// Longest, then leftmost.
+    Longest,
+}
+
+impl TieBreak {
+    /// Whether `a` is preferred to `b`, both having the same sum.
+    fn prefers<T>(&self, a: &Subarray<T>, b: &Subarray<T>) -> bool {
+        match self {
+            TieBreak::Leftmost => (a.start, a.end) < (b.start, b.end),
+            TieBreak::Shortest => (a.len(), a.start) < (b.len(), b.start),
+            TieBreak::Longest => (b.len(), a.start) < (a.len(), b.start),
+        }
+    }
+
+    /// Whether `a` is better than `b`.
+    pub(crate) fn better<T: Num>(&self, a: &Subarray<T>, b: &Subarray<T>) -> bool {
+        a.sum > b.sum || (a.sum == b.sum && self.prefers(a, b))
+    }
+}
+
+/// Kadane's algorithm, O(n), with a choice of tie-breaking and of whether
+/// the empty subarray (with sum zero) is a valid answer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kadane {
+    tie_break: TieBreak,
+    allow_empty: bool,
+}
+
+impl Kadane {
+    /// Leftmost non-empty maximum subarray.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Lets the empty subarray `0..0` be the answer, so the sum is never
+    /// negative.
+    pub fn allow_empty(mut self) -> Self {
+        self.allow_empty = true;
+        self
+    }
+
+    /// The maximum subarray of `nums`, `None` only if `nums` is empty and the
+    /// empty subarray is not allowed.
+    ///
+    /// The best subarray ending at each index extends the one ending at the
+    /// previous index if its sum is positive, or zero unless looking for the
+    /// shortest answer, and starts over otherwise.
+    pub fn run<T: Num>(&self, nums: &[T]) -> Option<Subarray<T>> {
+        let mut best = self.allow_empty.then_some(Subarray {
+            sum: T::ZERO,
+            start: 0,
+            end: 0,
+        });
+        let mut current: Option<Subarray<T>> = None;
+        for (i, el) in nums.iter().enumerate() {
+            let extend = current.is_some_and(|c| {
+                c.sum > T::ZERO || (c.sum == T::ZERO && self.tie_break != TieBreak::Shortest)
+            });
+            current = Some(match current {
+                Some(c) if extend => Subarray {
+                    sum: c.sum + *el,
+                    start: c.start,
+                    end: i + 1,
+                },
+                _ => Subarray {
+                    sum: *el,
+                    start: i,
+                    end: i + 1,
+                },
+            });
+            let candidate = current.unwrap();
+            if best.is_none_or(|b| self.tie_break.better(&candidate, &b)) {
+                best = Some(candidate);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    /// Best of all the subarrays, by the same ordering.
+    fn brute_force(nums: &[i64], kadane: &Kadane) -> Option<Subarray<i64>> {
+        let first = if kadane.allow_empty { 0 } else { 1 };
+        let mut best: Option<Subarray<i64>> = None;
+        for start in 0..=nums.len() {
+            for end in start + first..=nums.len() {
+                let candidate = Subarray {
+                    sum: nums[start..end].iter().sum(),
+                    start,
+                    end,
+                };
+                if best.is_none_or(|b| kadane.tie_break.better(&candidate, &b)) {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn tie_breaking_policies() {
+        let nums = [2, -2, 2, 0, -5, 2];
+        let run = |tie_break| Kadane::new().tie_break(tie_break).run(&nums).unwrap();
+        assert_eq!(run(TieBreak::Leftmost).range(), 0..1);
+        assert_eq!(run(TieBreak::Shortest).range(), 0..1);
+        assert_eq!(run(TieBreak::Longest).range(), 0..4);
+        assert_eq!(run(TieBreak::Longest).sum, 2);
+    }
+
+    #[test]
+    fn empty_subarray_and_input() {
+        assert_eq!(Kadane::new().run::<i32>(&[]), None);
+        let empty = Kadane::new().allow_empty().run(&[-3, -1]).unwrap();
+        assert_eq!((empty.sum, empty.range()), (0, 0..0));
+        assert!(empty.is_empty());
+        assert_eq!(Kadane::new().run(&[-3, -1]).unwrap().range(), 1..2);
+        assert!(Kadane::new()
+            .allow_empty()
+            .run::<i8>(&[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn floats() {
+        let best = Kadane::new().run(&[-1.5, 2.5, -0.5, 1.0, -4.0]).unwrap();
+        assert_eq!((best.sum, best.range()), (3.0, 1..4));
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(46);
+        let policies = [TieBreak::Leftmost, TieBreak::Shortest, TieBreak::Longest];
+        for _ in 0..300 {
+            let len = rng.below(12) as usize;
+            let nums = rng.vec(len, -4, 4);
+            for tie_break in policies {
+                for kadane in [
+                    Kadane::new().tie_break(tie_break),
+                    Kadane::new().tie_break(tie_break).allow_empty(),
+                ] {
+                    assert_eq!(kadane.run(&nums), brute_force(&nums, &kadane), "{nums:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+pub mod kadane;
+pub mod num;
+pub mod rectangle;
+pub mod segment_tree;
+pub mod top_k;
+pub mod variants;
+
+use kadane::{Kadane, Subarray};
+use num::Num;
+
+/// Leftmost non-empty subarray of `nums` with the maximum sum, `None` if
+/// `nums` is empty.
+pub fn maximum_subarray<T: Num>(nums: &[T]) -> Option<Subarray<T>> {
+    Kadane::new().run(nums)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leetcode_example() {
+        let best = maximum_subarray(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]).unwrap();
+        assert_eq!((best.sum, best.start, best.end), (6, 3, 7));
+        assert_eq!(maximum_subarray::<i32>(&[]), None);
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+
+/// Signed integer or float element of the arrays.
+///
+/// Floats are only compared with `PartialOrd`, so NaN elements give
+/// unspecified results.
+pub trait Num: Copy + Debug + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+
+    /// Nearest `f64`, which loses precision for integers beyond 2^53.
+    fn to_f64(self) -> f64;
+
+    /// The exact value for integer types, `None` for floats.
+    fn to_i128(self) -> Option<i128>;
+}
+
+macro_rules! impl_num {
+    (int: $($i:ty),*; float: $($f:ty),*) => {
+        $(impl Num for $i {
+            const ZERO: Self = 0;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_i128(self) -> Option<i128> {
+                Some(self as i128)
+            }
+        })*
+        $(impl Num for $f {
+            const ZERO: Self = 0.0;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_i128(self) -> Option<i128> {
+                None
+            }
+        })*
+    };
+}
+
+impl_num!(int: i8, i16, i32, i64, i128, isize; float: f32, f64);
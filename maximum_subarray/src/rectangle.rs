@@ -0,0 +1,206 @@
+use crate::num::Num;
+use crate::variants::max_subarray_with_length;
+
+/// Sub-rectangle `rows x cols` of a matrix and its sum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle<T> {
+    pub sum: T,
+    pub top: usize,
+    pub left: usize,
+    /// One past the last row.
+    pub bottom: usize,
+    /// One past the last column.
+    pub right: usize,
+}
+
+impl<T> Rectangle<T> {
+    pub fn rows(&self) -> std::ops::Range<usize> {
+        self.top..self.bottom
+    }
+
+    pub fn cols(&self) -> std::ops::Range<usize> {
+        self.left..self.right
+    }
+
+    pub fn height(&self) -> usize {
+        self.bottom - self.top
+    }
+
+    pub fn width(&self) -> usize {
+        self.right - self.left
+    }
+}
+
+/// Maximum sum sub-rectangle of a matrix, optionally with bounds on its
+/// width and height. Ties go to the smallest `(top, left, bottom, right)`.
+///
+/// For every pair of top and bottom rows the columns are summed into one
+/// array, whose maximum subarray of admissible width is found in O(m), for
+/// O(n^2 m) overall on an `n x m` matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxRectangle {
+    min_width: usize,
+    max_width: usize,
+    min_height: usize,
+    max_height: usize,
+}
+
+impl MaxRectangle {
+    /// Any non-empty rectangle.
+    pub fn new() -> Self {
+        Self {
+            min_width: 1,
+            max_width: usize::MAX,
+            min_height: 1,
+            max_height: usize::MAX,
+        }
+    }
+
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.min_width = width.max(1);
+        self
+    }
+
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    pub fn min_height(mut self, height: usize) -> Self {
+        self.min_height = height.max(1);
+        self
+    }
+
+    pub fn max_height(mut self, height: usize) -> Self {
+        self.max_height = height;
+        self
+    }
+
+    /// The best rectangle of `matrix`, `None` if none fits the bounds.
+    pub fn run<T: Num>(&self, matrix: &[Vec<T>]) -> Option<Rectangle<T>> {
+        let n = matrix.len();
+        let m = matrix.first().map_or(0, Vec::len);
+        assert!(
+            matrix.iter().all(|row| row.len() == m),
+            "matrix rows must have the same length"
+        );
+        let mut best: Option<Rectangle<T>> = None;
+        for top in 0..n {
+            let mut columns = vec![T::ZERO; m];
+            for bottom in top + 1..=n.min(top.saturating_add(self.max_height)) {
+                for (sum, el) in columns.iter_mut().zip(&matrix[bottom - 1]) {
+                    *sum = *sum + *el;
+                }
+                if bottom - top < self.min_height {
+                    continue;
+                }
+                let Some(cols) = max_subarray_with_length(&columns, self.min_width, self.max_width)
+                else {
+                    continue;
+                };
+                let candidate = Rectangle {
+                    sum: cols.sum,
+                    top,
+                    left: cols.start,
+                    bottom,
+                    right: cols.end,
+                };
+                if best.is_none_or(|b| better(&candidate, &b)) {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best
+    }
+}
+
+impl Default for MaxRectangle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn better<T: Num>(a: &Rectangle<T>, b: &Rectangle<T>) -> bool {
+    a.sum > b.sum
+        || (a.sum == b.sum
+            && (a.top, a.left, a.bottom, a.right) < (b.top, b.left, b.bottom, b.right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    fn brute_force(matrix: &[Vec<i64>], bounds: &MaxRectangle) -> Option<Rectangle<i64>> {
+        let n = matrix.len();
+        let m = matrix.first().map_or(0, Vec::len);
+        let mut best: Option<Rectangle<i64>> = None;
+        for top in 0..n {
+            for left in 0..m {
+                for bottom in top + 1..=n {
+                    for right in left + 1..=m {
+                        let candidate = Rectangle {
+                            sum: matrix[top..bottom]
+                                .iter()
+                                .map(|row| row[left..right].iter().sum::<i64>())
+                                .sum(),
+                            top,
+                            left,
+                            bottom,
+                            right,
+                        };
+                        let fits = (bounds.min_width..=bounds.max_width)
+                            .contains(&candidate.width())
+                            && (bounds.min_height..=bounds.max_height)
+                                .contains(&candidate.height());
+                        if fits && best.is_none_or(|b| better(&candidate, &b)) {
+                            best = Some(candidate);
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn classic_example() {
+        let matrix = vec![
+            vec![0, -2, -7, 0],
+            vec![9, 2, -6, 2],
+            vec![-4, 1, -4, 1],
+            vec![-1, 8, 0, -2],
+        ];
+        let best = MaxRectangle::new().run(&matrix).unwrap();
+        assert_eq!((best.sum, best.rows(), best.cols()), (15, 1..4, 0..2));
+
+        let thin = MaxRectangle::new().max_width(1).run(&matrix).unwrap();
+        assert_eq!((thin.sum, thin.rows(), thin.cols()), (11, 1..4, 1..2));
+        let wide = MaxRectangle::new().min_width(3).max_height(2);
+        let wide = wide.run(&matrix).unwrap();
+        assert_eq!((wide.sum, wide.rows(), wide.cols()), (7, 1..2, 0..4));
+
+        assert_eq!(MaxRectangle::new().min_height(5).run(&matrix), None);
+        assert_eq!(MaxRectangle::new().run::<i32>(&[]), None);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(48);
+        for _ in 0..200 {
+            let n = rng.below(5) as usize + 1;
+            let m = rng.below(5) as usize + 1;
+            let matrix: Vec<Vec<i64>> = (0..n).map(|_| rng.vec(m, -5, 5)).collect();
+            let bounds = MaxRectangle::new()
+                .min_width(rng.below(3) as usize)
+                .max_width(rng.below(5) as usize + 1)
+                .min_height(rng.below(3) as usize)
+                .max_height(rng.below(5) as usize + 1);
+            assert_eq!(
+                bounds.run(&matrix),
+                brute_force(&matrix, &bounds),
+                "{matrix:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::kadane::{Subarray, TieBreak};
+use crate::num::Num;
+
+/// Summary of a range of the array: its total, its best prefix, suffix and
+/// subarray, ties going to the leftmost ones.
+#[derive(Debug, Clone, Copy)]
+struct Node<T> {
+    total: Subarray<T>,
+    prefix: Subarray<T>,
+    suffix: Subarray<T>,
+    best: Subarray<T>,
+}
+
+impl<T: Num> Node<T> {
+    fn leaf(i: usize, value: T) -> Self {
+        let single = Subarray {
+            sum: value,
+            start: i,
+            end: i + 1,
+        };
+        Self {
+            total: single,
+            prefix: single,
+            suffix: single,
+            best: single,
+        }
+    }
+
+    fn combine(a: &Self, b: &Self) -> Self {
+        let join = |a: &Subarray<T>, b: &Subarray<T>| Subarray {
+            sum: a.sum + b.sum,
+            start: a.start,
+            end: b.end,
+        };
+        let pick = |a: Subarray<T>, b: Subarray<T>| {
+            if TieBreak::Leftmost.better(&b, &a) {
+                b
+            } else {
+                a
+            }
+        };
+        Self {
+            total: join(&a.total, &b.total),
+            prefix: pick(a.prefix, join(&a.total, &b.prefix)),
+            suffix: pick(join(&a.suffix, &b.total), b.suffix),
+            best: pick(pick(a.best, join(&a.suffix, &b.prefix)), b.best),
+        }
+    }
+}
+
+/// Maximum subarray queries over ranges of an array with point updates, both
+/// O(log n).
+///
+/// Laid out like the `SegmentTree` of the segment_tree crate: children of
+/// `id` at `2 * id + 1` and `2 * id + 2`, leaves from `capacity / 2`, and
+/// `None` for the padding leaves past the end of the array.
+#[derive(Debug, Clone)]
+pub struct MaxSubarrayTree<T> {
+    nodes: Vec<Option<Node<T>>>,
+    size: usize,
+    capacity: usize,
+}
+
+impl<T: Num> MaxSubarrayTree<T> {
+    pub fn new(nums: &[T]) -> Self {
+        let capacity = nums.len().next_power_of_two() * 2 - 1;
+        let mut tree = Self {
+            nodes: vec![None; capacity],
+            size: nums.len(),
+            capacity,
+        };
+        for (i, el) in nums.iter().enumerate() {
+            tree.nodes[capacity / 2 + i] = Some(Node::leaf(i, *el));
+        }
+        for id in (0..capacity / 2).rev() {
+            tree.pull(id);
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        assert!(i < self.size, "index out of bounds");
+        self.nodes[self.capacity / 2 + i].unwrap().total.sum
+    }
+
+    pub fn set(&mut self, i: usize, value: T) {
+        assert!(i < self.size, "index out of bounds");
+        let mut cur = self.capacity / 2 + i;
+        self.nodes[cur] = Some(Node::leaf(i, value));
+        while cur > 0 {
+            cur = Self::parent(cur);
+            self.pull(cur);
+        }
+    }
+
+    /// Leftmost non-empty subarray of `nums[l..r]` with the maximum sum,
+    /// `None` if the range is empty.
+    pub fn max_subarray(&self, l: usize, r: usize) -> Option<Subarray<T>> {
+        assert!(l <= r && r <= self.size, "invalid range");
+        self.query(0, 0, self.capacity / 2 + 1, l, r)
+            .map(|node| node.best)
+    }
+
+    fn left_child(id: usize) -> usize {
+        id * 2 + 1
+    }
+
+    fn right_child(id: usize) -> usize {
+        id * 2 + 2
+    }
+
+    fn parent(id: usize) -> usize {
+        (id - 1) / 2
+    }
+
+    fn pull(&mut self, id: usize) {
+        let (l, r) = (Self::left_child(id), Self::right_child(id));
+        self.nodes[id] = Self::merge(self.nodes[l], self.nodes[r]);
+    }
+
+    fn merge(a: Option<Node<T>>, b: Option<Node<T>>) -> Option<Node<T>> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(Node::combine(&a, &b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+
+    fn query(&self, id: usize, lo: usize, hi: usize, l: usize, r: usize) -> Option<Node<T>> {
+        if r <= lo || hi <= l {
+            return None;
+        }
+        if l <= lo && hi <= r {
+            return self.nodes[id];
+        }
+        let mid = (lo + hi) / 2;
+        Self::merge(
+            self.query(Self::left_child(id), lo, mid, l, r),
+            self.query(Self::right_child(id), mid, hi, l, r),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kadane::Kadane;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example_ranges() {
+        let mut tree = MaxSubarrayTree::new(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]);
+        assert_eq!(tree.max_subarray(0, 9).unwrap().range(), 3..7);
+        assert_eq!(tree.max_subarray(0, 3).unwrap().range(), 1..2);
+        assert_eq!(tree.max_subarray(4, 4), None);
+        tree.set(7, 5);
+        let best = tree.max_subarray(0, 9).unwrap();
+        assert_eq!((best.sum, best.range()), (15, 3..9));
+        assert_eq!(tree.get(7), 5);
+    }
+
+    #[test]
+    fn random_updates_against_kadane() {
+        let mut rng = Rng::new(47);
+        for _ in 0..100 {
+            let len = rng.below(20) as usize + 1;
+            let mut nums = rng.vec(len, -5, 5);
+            let mut tree = MaxSubarrayTree::new(&nums);
+            for _ in 0..20 {
+                let i = rng.below(len as u64) as usize;
+                nums[i] = rng.range(-5, 5);
+                tree.set(i, nums[i]);
+                let l = rng.below(len as u64 + 1) as usize;
+                let r = l + rng.below((len - l) as u64 + 1) as usize;
+                let expected = Kadane::new().run(&nums[l..r]).map(|b| Subarray {
+                    sum: b.sum,
+                    start: b.start + l,
+                    end: b.end + l,
+                });
+                assert_eq!(tree.max_subarray(l, r), expected, "{:?}", &nums[l..r]);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::kadane::Subarray;
+use crate::num::Num;
+use crate::variants::prefix_sums;
+
+/// Positions of the smallest element of every range of length `2^k`,
+/// the leftmost on ties.
+struct SparseArgMin<'a, T> {
+    values: &'a [T],
+    table: Vec<Vec<usize>>,
+}
+
+impl<'a, T: Num> SparseArgMin<'a, T> {
+    fn new(values: &'a [T]) -> Self {
+        let mut table = vec![(0..values.len()).collect::<Vec<usize>>()];
+        let mut width = 1;
+        while 2 * width <= values.len() {
+            let last = table.last().unwrap();
+            let next = (0..=values.len() - 2 * width)
+                .map(|i| Self::pick(values, last[i], last[i + width]))
+                .collect();
+            table.push(next);
+            width *= 2;
+        }
+        Self { values, table }
+    }
+
+    fn pick(values: &[T], a: usize, b: usize) -> usize {
+        if values[b] < values[a] {
+            b
+        } else {
+            a
+        }
+    }
+
+    /// Position of the smallest of `values[l..=r]`.
+    fn query(&self, l: usize, r: usize) -> usize {
+        let k = (r - l + 1).ilog2() as usize;
+        let (a, b) = (self.table[k][l], self.table[k][r + 1 - (1 << k)]);
+        Self::pick(self.values, a, b)
+    }
+}
+
+/// Best subarray ending at `subarray.end` among the starts `lo..=hi`.
+struct Candidate<T> {
+    subarray: Subarray<T>,
+    lo: usize,
+    hi: usize,
+}
+
+impl<T: Num> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.subarray, &other.subarray);
+        a.sum
+            .partial_cmp(&b.sum)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| (b.start, b.end).cmp(&(a.start, a.end)))
+    }
+}
+
+impl<T: Num> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Num> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Num> Eq for Candidate<T> {}
+
+/// The `k` non-empty subarrays with the largest sums, in decreasing order of
+/// sum and leftmost first on ties. Fewer are returned if `nums` has less
+/// than `k` subarrays.
+///
+/// For each end, the best start is the one with the smallest prefix sum, found
+/// in O(1) with a sparse table. A max-heap holds the best subarray of each
+/// end; popping one splits its range of starts around the start it used, so
+/// the next best subarrays with that end join the heap. O((n + k) log n).
+pub fn top_k_subarray_sums<T: Num>(nums: &[T], k: usize) -> Vec<Subarray<T>> {
+    let prefix = prefix_sums(nums);
+    let starts = SparseArgMin::new(&prefix);
+    let candidate = |end: usize, lo: usize, hi: usize| {
+        let start = starts.query(lo, hi);
+        Candidate {
+            subarray: Subarray {
+                sum: prefix[end] - prefix[start],
+                start,
+                end,
+            },
+            lo,
+            hi,
+        }
+    };
+
+    let mut heap: BinaryHeap<Candidate<T>> = (1..=nums.len())
+        .map(|end| candidate(end, 0, end - 1))
+        .collect();
+    let mut result = Vec::with_capacity(k.min(heap.len()));
+    while result.len() < k {
+        let Some(Candidate { subarray, lo, hi }) = heap.pop() else {
+            break;
+        };
+        if lo < subarray.start {
+            heap.push(candidate(subarray.end, lo, subarray.start - 1));
+        }
+        if subarray.start < hi {
+            heap.push(candidate(subarray.end, subarray.start + 1, hi));
+        }
+        result.push(subarray);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example_top_sums() {
+        let top = top_k_subarray_sums(&[-2, 1, -3, 4, -1, 2, 1, -5, 4], 4);
+        let summary: Vec<(i32, usize, usize)> =
+            top.iter().map(|s| (s.sum, s.start, s.end)).collect();
+        assert_eq!(summary, vec![(6, 3, 7), (5, 3, 6), (5, 3, 9), (4, 1, 7)]);
+        assert_eq!(top_k_subarray_sums(&[1, 2], 10).len(), 3);
+        assert!(top_k_subarray_sums::<i32>(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(50);
+        for _ in 0..200 {
+            let len = rng.below(12) as usize;
+            let nums = rng.vec(len, -5, 5);
+            let mut all: Vec<Subarray<i64>> = (0..len)
+                .flat_map(|start| (start + 1..=len).map(move |end| (start, end)))
+                .map(|(start, end)| Subarray {
+                    sum: nums[start..end].iter().sum(),
+                    start,
+                    end,
+                })
+                .collect();
+            all.sort_by_key(|s| (std::cmp::Reverse(s.sum), s.start, s.end));
+            let k = rng.below(80) as usize;
+            all.truncate(k);
+            assert_eq!(top_k_subarray_sums(&nums, k), all, "{nums:?}");
+        }
+    }
+}
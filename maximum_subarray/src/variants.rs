@@ -0,0 +1,271 @@
+use std::collections::VecDeque;
+
+use crate::kadane::{Kadane, Subarray, TieBreak};
+use crate::num::Num;
+
+/// `prefix[i]` is the sum of `nums[..i]`.
+pub(crate) fn prefix_sums<T: Num>(nums: &[T]) -> Vec<T> {
+    let mut prefix = vec![T::ZERO; nums.len() + 1];
+    for (i, el) in nums.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + *el;
+    }
+    prefix
+}
+
+/// Leftmost maximum subarray of the circular array `nums`, `None` if `nums`
+/// is empty.
+///
+/// The result covers the indices `start..end` taken modulo `nums.len()`, so
+/// `end` exceeds the length when it wraps around. A wrapping subarray is the
+/// whole array minus `start - n..end - n`: for each `start` the best one
+/// leaves out the largest prefix sum before it. O(n).
+pub fn max_circular_subarray<T: Num>(nums: &[T]) -> Option<Subarray<T>> {
+    let n = nums.len();
+    let prefix = prefix_sums(nums);
+    let mut best = Kadane::new().run(nums)?;
+    let mut kept: Option<usize> = None;
+    for start in 1..n {
+        if kept.is_none_or(|k| prefix[start] > prefix[k]) {
+            kept = Some(start);
+        }
+        let end = kept.unwrap();
+        let candidate = Subarray {
+            sum: prefix[n] - prefix[start] + prefix[end],
+            start,
+            end: end + n,
+        };
+        if TieBreak::Leftmost.better(&candidate, &best) {
+            best = candidate;
+        }
+    }
+    Some(best)
+}
+
+/// Leftmost maximum subarray whose length is in `min_len..=max_len`, `None`
+/// if there is none. A `min_len` of zero is treated as one.
+///
+/// The best start for each end is the smallest prefix sum among the allowed
+/// starts, a window sliding with the end and kept in a monotonic deque, so
+/// this is O(n).
+pub fn max_subarray_with_length<T: Num>(
+    nums: &[T],
+    min_len: usize,
+    max_len: usize,
+) -> Option<Subarray<T>> {
+    let min_len = min_len.max(1);
+    let prefix = prefix_sums(nums);
+    let mut best: Option<Subarray<T>> = None;
+    let mut starts: VecDeque<usize> = VecDeque::new();
+    for end in min_len..=nums.len() {
+        let start = end - min_len;
+        while starts.back().is_some_and(|s| prefix[*s] > prefix[start]) {
+            starts.pop_back();
+        }
+        starts.push_back(start);
+        if starts.front().is_some_and(|s| end - s > max_len) {
+            starts.pop_front();
+        }
+        let Some(start) = starts.front().copied() else {
+            continue;
+        };
+        let candidate = Subarray {
+            sum: prefix[end] - prefix[start],
+            start,
+            end,
+        };
+        if best.is_none_or(|b| TieBreak::Leftmost.better(&candidate, &b)) {
+            best = Some(candidate);
+        }
+    }
+    best
+}
+
+/// A subarray of length at least `min_len` with the maximum average
+/// `sum / len`, `None` if `nums` is shorter than `min_len`.
+///
+/// Binary search on the average `x`: some subarray reaches it if the best
+/// sum of `nums[i] - x` over the allowed lengths is non-negative. Each step
+/// is O(n), and the search stops once the bounds meet in floating point.
+///
+/// The search runs on [`Num::to_f64`], so for integers it is followed by an
+/// exact check in `i128`: while some subarray has a strictly higher average
+/// `p / q` than the current one, found as a positive sum of `q * nums[i] - p`,
+/// it becomes the current one. Float inputs are only optimal up to rounding,
+/// as are `i128` inputs too large for the check.
+pub fn max_average_subarray<T: Num>(nums: &[T], min_len: usize) -> Option<Subarray<T>> {
+    let min_len = min_len.max(1);
+    if nums.len() < min_len {
+        return None;
+    }
+    let values: Vec<f64> = nums.iter().map(|el| el.to_f64()).collect();
+    // Start of a subarray of length >= min_len with average >= x, if any.
+    let reaching = |x: f64| -> Option<(usize, usize)> {
+        let shifted: Vec<f64> = values.iter().map(|v| v - x).collect();
+        let prefix = prefix_sums(&shifted);
+        let mut lowest = 0;
+        for end in min_len..=nums.len() {
+            if prefix[end - min_len] < prefix[lowest] {
+                lowest = end - min_len;
+            }
+            if prefix[end] - prefix[lowest] >= 0.0 {
+                return Some((lowest, end));
+            }
+        }
+        None
+    };
+
+    let mut lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut found = reaching(lo).expect("the minimum is always reached");
+    loop {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        match reaching(mid) {
+            Some(range) => (lo, found) = (mid, range),
+            None => hi = mid,
+        }
+    }
+    if let Some(exact) = nums
+        .iter()
+        .map(|el| el.to_i128())
+        .collect::<Option<Vec<_>>>()
+    {
+        found = improve_average(&exact, min_len, found);
+    }
+    let (start, end) = found;
+    let sum = nums[start + 1..end]
+        .iter()
+        .fold(nums[start], |sum, el| sum + *el);
+    Some(Subarray { sum, start, end })
+}
+
+/// Replaces `start..end` with subarrays of strictly higher average until
+/// none is left, or the arithmetic would overflow.
+fn improve_average(nums: &[i128], min_len: usize, mut best: (usize, usize)) -> (usize, usize) {
+    let prefix = prefix_sums(nums);
+    loop {
+        let (start, end) = best;
+        let (p, q) = (prefix[end] - prefix[start], (end - start) as i128);
+        // q * prefix[i] - p * i, the prefix sums of q * nums[i] - p.
+        let shifted: Option<Vec<i128>> = (0..prefix.len())
+            .map(|i| {
+                q.checked_mul(prefix[i])?
+                    .checked_sub(p.checked_mul(i as i128)?)
+            })
+            .collect();
+        let Some(shifted) = shifted else {
+            return best;
+        };
+        let mut lowest = 0;
+        let mut improved: Option<(i128, usize, usize)> = None;
+        for end in min_len..=nums.len() {
+            if shifted[end - min_len] < shifted[lowest] {
+                lowest = end - min_len;
+            }
+            let Some(gain) = shifted[end].checked_sub(shifted[lowest]) else {
+                return best;
+            };
+            if gain > 0 && improved.is_none_or(|(g, _, _)| gain > g) {
+                improved = Some((gain, lowest, end));
+            }
+        }
+        match improved {
+            Some((_, start, end)) => best = (start, end),
+            None => return best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    /// Every subarray, wrapping around if `circular`.
+    fn all_subarrays(nums: &[i64], circular: bool) -> Vec<Subarray<i64>> {
+        let n = nums.len();
+        let mut result = Vec::new();
+        for start in 0..n {
+            let last = if circular { start + n } else { n };
+            for end in start + 1..=last {
+                let sum = (start..end).map(|i| nums[i % n]).sum();
+                result.push(Subarray { sum, start, end });
+            }
+        }
+        result
+    }
+
+    fn leftmost_best(candidates: impl Iterator<Item = Subarray<i64>>) -> Option<Subarray<i64>> {
+        candidates.fold(None, |best, c| match best {
+            Some(b) if !TieBreak::Leftmost.better(&c, &b) => Some(b),
+            _ => Some(c),
+        })
+    }
+
+    #[test]
+    fn leetcode_examples() {
+        let best = max_circular_subarray(&[5, -3, 5]).unwrap();
+        assert_eq!((best.sum, best.range()), (10, 2..4));
+        assert_eq!(max_circular_subarray(&[-3, -2, -3]).unwrap().sum, -2);
+        assert_eq!(max_circular_subarray::<i32>(&[]), None);
+
+        let best = max_subarray_with_length(&[-2, 1, -3, 4, -1, 2, 1, -5, 4], 5, 9).unwrap();
+        assert_eq!((best.sum, best.range()), (5, 3..9));
+        assert_eq!(max_subarray_with_length(&[1, 2], 3, 4), None);
+
+        let best = max_average_subarray(&[1, 12, -5, -6, 50, 3], 4).unwrap();
+        assert_eq!((best.sum, best.range()), (51, 1..5));
+        assert_eq!(max_average_subarray(&[1, 2], 3), None);
+
+        let big: [i64; 3] = [1 << 60, (1 << 60) + 1, 1 << 60];
+        assert_eq!(max_average_subarray(&big, 1).unwrap().range(), 1..2);
+        let best = max_average_subarray(&big, 2).unwrap();
+        assert_eq!((best.sum, best.len()), ((1 << 61) + 1, 2));
+        let best = max_average_subarray(&[-0.5, 2.0, 1.0, -3.0], 2).unwrap();
+        assert_eq!(best.range(), 1..3);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(49);
+        for _ in 0..300 {
+            let len = rng.below(10) as usize;
+            let nums = rng.vec(len, -6, 6);
+
+            let expected = leftmost_best(all_subarrays(&nums, true).into_iter());
+            assert_eq!(max_circular_subarray(&nums), expected, "{nums:?}");
+
+            let min_len = rng.below(5) as usize;
+            let max_len = min_len + rng.below(5) as usize;
+            let expected = leftmost_best(
+                all_subarrays(&nums, false)
+                    .into_iter()
+                    .filter(|s| (min_len.max(1)..=max_len).contains(&s.len())),
+            );
+            assert_eq!(
+                max_subarray_with_length(&nums, min_len, max_len),
+                expected,
+                "{nums:?}"
+            );
+
+            let min_len = rng.below(4) as usize + 1;
+            let got = max_average_subarray(&nums, min_len);
+            let admissible = all_subarrays(&nums, false).into_iter();
+            let best = admissible
+                .filter(|s| s.len() >= min_len)
+                .max_by(|a, b| (a.sum * b.len() as i64).cmp(&(b.sum * a.len() as i64)));
+            assert_eq!(got.is_some(), best.is_some(), "{nums:?}");
+            if let (Some(got), Some(best)) = (got, best) {
+                assert!(got.len() >= min_len);
+                assert_eq!(got.sum, nums[got.range()].iter().sum::<i64>());
+                assert_eq!(
+                    got.sum * best.len() as i64,
+                    best.sum * got.len() as i64,
+                    "{nums:?}"
+                );
+            }
+        }
+    }
+}
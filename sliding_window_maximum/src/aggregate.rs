@@ -0,0 +1,339 @@
+use std::marker::PhantomData;
+use std::ops::Add;
+
+/// Associative operation with an identity element.
+///
+/// The operation takes `&self` so that monoids carrying parameters (a modulus,
+/// a matrix size, ...) can be built at runtime.
+pub trait Monoid {
+    type Value: Clone;
+
+    fn identity(&self) -> Self::Value;
+    fn combine(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+/// Sum of the elements, with `T::default()` as identity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum<T>(PhantomData<T>);
+
+impl<T> Sum<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Clone + Default + Add<Output = T>> Monoid for Sum<T> {
+    type Value = T;
+
+    fn identity(&self) -> T {
+        T::default()
+    }
+
+    fn combine(&self, a: &T, b: &T) -> T {
+        a.clone() + b.clone()
+    }
+}
+
+/// Maximum of the elements, `None` for an empty range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max<T>(PhantomData<T>);
+
+impl<T> Max<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Ord + Clone> Monoid for Max<T> {
+    type Value = Option<T>;
+
+    fn identity(&self) -> Option<T> {
+        None
+    }
+
+    fn combine(&self, a: &Option<T>, b: &Option<T>) -> Option<T> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.max(b).clone()),
+            (Some(x), None) | (None, Some(x)) => Some(x.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Minimum of the elements, `None` for an empty range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min<T>(PhantomData<T>);
+
+impl<T> Min<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Ord + Clone> Monoid for Min<T> {
+    type Value = Option<T>;
+
+    fn identity(&self) -> Option<T> {
+        None
+    }
+
+    fn combine(&self, a: &Option<T>, b: &Option<T>) -> Option<T> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b).clone()),
+            (Some(x), None) | (None, Some(x)) => Some(x.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Greatest common divisor, with 0 as identity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gcd;
+
+impl Monoid for Gcd {
+    type Value = u64;
+
+    fn identity(&self) -> u64 {
+        0
+    }
+
+    fn combine(&self, a: &u64, b: &u64) -> u64 {
+        let (mut a, mut b) = (*a, *b);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
+
+/// Summary of a non-empty range used to find its maximum subarray sum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subarray {
+    pub total: i64,
+    pub prefix: i64,
+    pub suffix: i64,
+    pub best: i64,
+}
+
+impl Subarray {
+    pub fn single(el: i64) -> Self {
+        Self {
+            total: el,
+            prefix: el,
+            suffix: el,
+            best: el,
+        }
+    }
+}
+
+/// Maximum sum of a non-empty subarray, `None` for an empty range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxSubarray;
+
+impl Monoid for MaxSubarray {
+    type Value = Option<Subarray>;
+
+    fn identity(&self) -> Option<Subarray> {
+        None
+    }
+
+    fn combine(&self, a: &Option<Subarray>, b: &Option<Subarray>) -> Option<Subarray> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(Subarray {
+                total: a.total + b.total,
+                prefix: a.prefix.max(a.total + b.prefix),
+                suffix: b.suffix.max(b.total + a.suffix),
+                best: a.best.max(b.best).max(a.suffix + b.prefix),
+            }),
+            (Some(x), None) | (None, Some(x)) => Some(*x),
+            (None, None) => None,
+        }
+    }
+}
+
+/// FIFO queue of monoid values answering the product of all of them, in
+/// order, in amortized O(1).
+///
+/// `front` holds the oldest elements together with the product from each of
+/// them to the end of `front`; `back` holds the newest ones and `back_agg`
+/// their product. When `front` runs out, `back` is flipped onto it.
+#[derive(Debug, Clone)]
+pub struct SlidingWindowAggregator<M: Monoid> {
+    monoid: M,
+    front: Vec<(M::Value, M::Value)>,
+    back: Vec<M::Value>,
+    back_agg: M::Value,
+}
+
+impl<M: Monoid> SlidingWindowAggregator<M> {
+    pub fn new(monoid: M) -> Self {
+        let back_agg = monoid.identity();
+        Self {
+            monoid,
+            front: Vec::new(),
+            back: Vec::new(),
+            back_agg,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn monoid(&self) -> &M {
+        &self.monoid
+    }
+
+    pub fn push_back(&mut self, el: M::Value) {
+        self.back_agg = self.monoid.combine(&self.back_agg, &el);
+        self.back.push(el);
+    }
+
+    pub fn pop_front(&mut self) -> Option<M::Value> {
+        if self.front.is_empty() {
+            let mut agg = self.monoid.identity();
+            while let Some(el) = self.back.pop() {
+                agg = self.monoid.combine(&el, &agg);
+                self.front.push((el, agg.clone()));
+            }
+            self.back_agg = self.monoid.identity();
+        }
+        self.front.pop().map(|(el, _)| el)
+    }
+
+    /// Product of all the queued elements, from the oldest to the newest.
+    pub fn query(&self) -> M::Value {
+        match self.front.last() {
+            Some((_, agg)) => self.monoid.combine(agg, &self.back_agg),
+            None => self.back_agg.clone(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_agg = self.monoid.identity();
+    }
+}
+
+/// Product of every window of `k` consecutive elements.
+pub fn sliding_window_fold<M: Monoid>(nums: &[M::Value], k: usize, monoid: M) -> Vec<M::Value> {
+    let mut result = Vec::new();
+    if k == 0 || k > nums.len() {
+        return result;
+    }
+    let mut window = SlidingWindowAggregator::new(monoid);
+
+    for el in nums {
+        window.push_back(el.clone());
+        if window.len() > k {
+            window.pop_front();
+        }
+        if window.len() == k {
+            result.push(window.query());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    /// 2x2 matrix product modulo 1000, which is not commutative.
+    struct MatMul;
+
+    impl Monoid for MatMul {
+        type Value = [[u64; 2]; 2];
+
+        fn identity(&self) -> Self::Value {
+            [[1, 0], [0, 1]]
+        }
+
+        fn combine(&self, a: &Self::Value, b: &Self::Value) -> Self::Value {
+            let mut c = [[0; 2]; 2];
+            for i in 0..2 {
+                for j in 0..2 {
+                    c[i][j] = (a[i][0] * b[0][j] + a[i][1] * b[1][j]) % 1000;
+                }
+            }
+            c
+        }
+    }
+
+    fn brute<M: Monoid>(nums: &[M::Value], k: usize, monoid: &M) -> Vec<M::Value> {
+        nums.windows(k)
+            .map(|w| {
+                w.iter()
+                    .fold(monoid.identity(), |acc, el| monoid.combine(&acc, el))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sums_and_gcds() {
+        assert_eq!(
+            sliding_window_fold(&[1, 2, 3, 4, 5], 2, Sum::new()),
+            vec![3, 5, 7, 9]
+        );
+        assert_eq!(
+            sliding_window_fold(&[12, 18, 24, 7, 14], 3, Gcd),
+            vec![6, 1, 1]
+        );
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(28);
+        for _ in 0..200 {
+            let len = rng.below(25) as usize + 1;
+            let k = rng.below(len as u64) as usize + 1;
+            let nums = rng.vec(len, -10, 10);
+
+            let opt: Vec<Option<i64>> = nums.iter().map(|x| Some(*x)).collect();
+            assert_eq!(
+                sliding_window_fold(&opt, k, Max::new()),
+                brute(&opt, k, &Max::new())
+            );
+            assert_eq!(
+                sliding_window_fold(&opt, k, Min::new()),
+                brute(&opt, k, &Min::new())
+            );
+
+            let summaries: Vec<_> = nums.iter().map(|x| Some(Subarray::single(*x))).collect();
+            let best: Vec<i64> = sliding_window_fold(&summaries, k, MaxSubarray)
+                .iter()
+                .map(|s| s.unwrap().best)
+                .collect();
+            let expected: Vec<i64> = nums
+                .windows(k)
+                .map(|w| {
+                    (0..k)
+                        .flat_map(|i| (i + 1..=k).map(move |j| w[i..j].iter().sum::<i64>()))
+                        .max()
+                        .unwrap()
+                })
+                .collect();
+            assert_eq!(best, expected);
+
+            let mats: Vec<_> = (0..len)
+                .map(|_| {
+                    [
+                        [rng.below(10), rng.below(10)],
+                        [rng.below(10), rng.below(10)],
+                    ]
+                })
+                .collect();
+            assert_eq!(
+                sliding_window_fold(&mats, k, MatMul),
+                brute(&mats, k, &MatMul)
+            );
+        }
+    }
+}
@@ -1,31 +1,340 @@
-struct Heap {
+/// Binary max-heap over `i32`.
+pub struct Heap {
     heap: Vec<i32>,
     size: usize,
 }
 
 impl Heap {
-    fn new() -> Heap {
+    pub fn new() -> Heap {
         Heap {
             heap: Vec::new(),
             size: 0,
         }
     }
 
-    fn push(&mut self, el: i32) {
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn push(&mut self, el: i32) {
         self.heap.push(el);
         self.size += 1;
         self.sift_up(self.size - 1);
     }
 
+    pub fn peek(&self) -> Option<i32> {
+        self.heap.first().copied()
+    }
+
+    pub fn pop(&mut self) -> Option<i32> {
+        if self.size == 0 {
+            return None;
+        }
+        self.size -= 1;
+        let top = self.heap.swap_remove(0);
+        if self.size > 0 {
+            self.sift_down(0);
+        }
+        Some(top)
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 && self.heap[self.parent(i)] < self.heap[i] {
+            let p = self.parent(i);
+            self.heap.swap(i, p);
+            i = p;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut largest = i;
+            for child in [self.left(i), self.right(i)] {
+                if child < self.size && self.heap[child] > self.heap[largest] {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                break;
+            }
+            self.heap.swap(i, largest);
+            i = largest;
+        }
+    }
+
     fn left(&self, i: usize) -> usize {
-        return 2 * i;
+        2 * i + 1
     }
 
     fn right(&self, i: usize) -> usize {
-        return 2 * i + 1;
+        2 * i + 2
     }
 
     fn parent(&self, i: usize) -> usize {
-        return i / 2;
+        (i - 1) / 2
+    }
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle to an element pushed into an [`IndexedHeap`].
+///
+/// Slots are reused once their element is removed, but each reuse bumps the
+/// slot's generation, so a handle to a removed element stays invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+impl Handle {
+    /// Slot of the element, below the largest number of elements the heap
+    /// has held at once.
+    pub fn index(self) -> usize {
+        self.slot
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    key: Option<T>,
+    /// Position inside `heap` while the slot holds a key.
+    pos: usize,
+    generation: u32,
+}
+
+/// Binary max-heap whose elements can be removed or rekeyed through the
+/// [`Handle`] returned by `push`.
+///
+/// `heap` stores slot numbers in heap order; `slots[s]` holds the key of slot
+/// `s` and its position inside `heap`. Freed slots are kept in `free` and
+/// reused, so memory is bounded by the largest size of the heap.
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<T> {
+    heap: Vec<usize>,
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+}
+
+impl<T: Ord> IndexedHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            heap: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Inserts `key` in O(log n) and returns a handle to it.
+    pub fn push(&mut self, key: T) -> Handle {
+        let pos = self.heap.len();
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                let s = &mut self.slots[slot];
+                s.generation = s.generation.wrapping_add(1);
+                s.key = Some(key);
+                s.pos = pos;
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    key: Some(key),
+                    pos,
+                    generation: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+        self.heap.push(slot);
+        self.sift_up(pos);
+        self.handle(slot)
+    }
+
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        let slot = *self.heap.first()?;
+        Some((self.handle(slot), self.slots[slot].key.as_ref()?))
+    }
+
+    pub fn pop(&mut self) -> Option<(Handle, T)> {
+        let handle = self.handle(*self.heap.first()?);
+        self.remove(handle).map(|key| (handle, key))
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.live(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.live(handle)?.key.as_ref()
+    }
+
+    /// Removes the element behind `handle` in O(log n), returning its key.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let i = self.live(handle)?.pos;
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        self.heap.pop();
+        if i < self.heap.len() {
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        self.free.push(handle.slot);
+        self.slots[handle.slot].key.take()
+    }
+
+    /// Replaces the key behind `handle` in O(log n), returning the old one.
+    pub fn change_key(&mut self, handle: Handle, key: T) -> Option<T> {
+        let i = self.live(handle)?.pos;
+        let old = self.slots[handle.slot].key.replace(key);
+        self.sift_up(i);
+        self.sift_down(i);
+        old
+    }
+
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// The slot behind `handle`, if it still holds that element.
+    fn live(&self, handle: Handle) -> Option<&Slot<T>> {
+        self.slots
+            .get(handle.slot)
+            .filter(|s| s.generation == handle.generation && s.key.is_some())
+    }
+
+    fn key(&self, i: usize) -> &T {
+        self.slots[self.heap[i]]
+            .key
+            .as_ref()
+            .expect("handles in the heap always have a key")
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.slots[self.heap[i]].pos = i;
+        self.slots[self.heap[j]].pos = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 && self.key((i - 1) / 2) < self.key(i) {
+            self.swap(i, (i - 1) / 2);
+            i = (i - 1) / 2;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut largest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.key(child) > self.key(largest) {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                break;
+            }
+            self.swap(i, largest);
+            i = largest;
+        }
+    }
+}
+
+impl<T: Ord> Default for IndexedHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn heap_pops_in_order() {
+        let mut heap = Heap::new();
+        for el in [5, 1, 8, -3, 8, 2] {
+            heap.push(el);
+        }
+        let mut popped = Vec::new();
+        while let Some(el) = heap.pop() {
+            popped.push(el);
+        }
+        assert_eq!(popped, vec![8, 8, 5, 2, 1, -3]);
+    }
+
+    #[test]
+    fn indexed_heap_remove_and_change_key() {
+        let mut heap = IndexedHeap::new();
+        let a = heap.push(4);
+        let b = heap.push(9);
+        let c = heap.push(7);
+        assert_eq!(heap.peek(), Some((b, &9)));
+
+        assert_eq!(heap.remove(b), Some(9));
+        assert!(!heap.contains(b));
+        assert_eq!(heap.remove(b), None);
+        assert_eq!(heap.peek(), Some((c, &7)));
+
+        assert_eq!(heap.change_key(a, 10), Some(4));
+        assert_eq!(heap.pop(), Some((a, 10)));
+        assert_eq!(heap.pop(), Some((c, 7)));
+        assert!(heap.is_empty());
+
+        let d = heap.push(3);
+        assert_eq!(d.index(), c.index());
+        assert!(!heap.contains(c));
+        assert_eq!(heap.change_key(c, 1), None);
+        assert_eq!(heap.get(d), Some(&3));
+    }
+
+    #[test]
+    fn indexed_heap_random_against_vec() {
+        let mut rng = Rng::new(26);
+        let mut heap = IndexedHeap::new();
+        let mut live: Vec<(Handle, i64)> = Vec::new();
+        let mut peak = 0;
+        for _ in 0..2000 {
+            match rng.below(4) {
+                0 | 1 => {
+                    let key = rng.range(-50, 50);
+                    live.push((heap.push(key), key));
+                }
+                2 if !live.is_empty() => {
+                    let (h, key) = live.swap_remove(rng.below(live.len() as u64) as usize);
+                    assert_eq!(heap.remove(h), Some(key));
+                }
+                _ if !live.is_empty() => {
+                    let i = rng.below(live.len() as u64) as usize;
+                    let key = rng.range(-50, 50);
+                    assert_eq!(heap.change_key(live[i].0, key), Some(live[i].1));
+                    live[i].1 = key;
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), live.len());
+            assert!(heap.slots.len() <= peak.max(live.len()));
+            peak = peak.max(live.len());
+            assert_eq!(heap.peek().map(|(_, k)| *k), live.iter().map(|e| e.1).max());
+        }
     }
 }
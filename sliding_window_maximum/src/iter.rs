@@ -0,0 +1,177 @@
+use crate::aggregate::{Monoid, SlidingWindowAggregator};
+use crate::monotonic::MonotonicDeque;
+
+/// Lazy sliding window adapters for any iterator, using O(k) memory.
+///
+/// By default one result is yielded per full window of `k` elements; call
+/// `partial()` on the adapter to also get the `k - 1` leading windows that
+/// are still filling up.
+pub trait SlidingWindowExt: Iterator + Sized {
+    /// Maximum of every window of `k` consecutive items.
+    fn sliding_max(self, k: usize) -> SlidingExtremum<Self>
+    where
+        Self::Item: Ord + Clone,
+    {
+        SlidingExtremum::new(self, k, MonotonicDeque::max())
+    }
+
+    /// Minimum of every window of `k` consecutive items.
+    fn sliding_min(self, k: usize) -> SlidingExtremum<Self>
+    where
+        Self::Item: Ord + Clone,
+    {
+        SlidingExtremum::new(self, k, MonotonicDeque::min())
+    }
+
+    /// Product under `monoid` of every window of `k` consecutive items.
+    fn sliding_fold<M>(self, k: usize, monoid: M) -> SlidingFold<Self, M>
+    where
+        M: Monoid<Value = Self::Item>,
+    {
+        SlidingFold {
+            iter: self,
+            window: SlidingWindowAggregator::new(monoid),
+            k,
+            partial: false,
+        }
+    }
+}
+
+impl<I: Iterator> SlidingWindowExt for I {}
+
+/// Iterator returned by [`SlidingWindowExt::sliding_max`] and
+/// [`SlidingWindowExt::sliding_min`].
+#[derive(Debug, Clone)]
+pub struct SlidingExtremum<I: Iterator> {
+    iter: I,
+    window: MonotonicDeque<I::Item>,
+    k: usize,
+    partial: bool,
+}
+
+impl<I: Iterator> SlidingExtremum<I>
+where
+    I::Item: Ord,
+{
+    fn new(iter: I, k: usize, window: MonotonicDeque<I::Item>) -> Self {
+        Self {
+            iter,
+            window,
+            k,
+            partial: false,
+        }
+    }
+
+    /// Also yields the leading windows shorter than `k`.
+    pub fn partial(mut self) -> Self {
+        self.partial = true;
+        self
+    }
+}
+
+impl<I: Iterator> Iterator for SlidingExtremum<I>
+where
+    I::Item: Ord + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.k == 0 {
+            return None;
+        }
+        loop {
+            self.window.push_back(self.iter.next()?);
+            if self.window.len() > self.k {
+                self.window.pop_front();
+            }
+            if self.partial || self.window.len() == self.k {
+                return self.window.best().cloned();
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`SlidingWindowExt::sliding_fold`].
+#[derive(Clone)]
+pub struct SlidingFold<I, M: Monoid> {
+    iter: I,
+    window: SlidingWindowAggregator<M>,
+    k: usize,
+    partial: bool,
+}
+
+impl<I, M: Monoid> SlidingFold<I, M> {
+    /// Also yields the leading windows shorter than `k`.
+    pub fn partial(mut self) -> Self {
+        self.partial = true;
+        self
+    }
+}
+
+impl<I, M> Iterator for SlidingFold<I, M>
+where
+    I: Iterator<Item = M::Value>,
+    M: Monoid,
+{
+    type Item = M::Value;
+
+    fn next(&mut self) -> Option<M::Value> {
+        if self.k == 0 {
+            return None;
+        }
+        loop {
+            self.window.push_back(self.iter.next()?);
+            if self.window.len() > self.k {
+                self.window.pop_front();
+            }
+            if self.partial || self.window.len() == self.k {
+                return Some(self.window.query());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Sum;
+    use crate::monotonic::{sliding_window_max, sliding_window_min};
+    use test_rng::Rng;
+
+    #[test]
+    fn full_and_partial_windows() {
+        let nums = [1, 3, -1, -3, 5, 3, 6, 7];
+        let max: Vec<i32> = nums.iter().copied().sliding_max(3).collect();
+        assert_eq!(max, vec![3, 3, 5, 5, 6, 7]);
+
+        let max: Vec<i32> = nums.iter().copied().sliding_max(3).partial().collect();
+        assert_eq!(max, vec![1, 3, 3, 3, 5, 5, 6, 7]);
+
+        let sums: Vec<i32> = (1..=5).sliding_fold(2, Sum::new()).partial().collect();
+        assert_eq!(sums, vec![1, 3, 5, 7, 9]);
+
+        assert_eq!((1..=5).sliding_min(0).count(), 0);
+        assert_eq!((1..=5).sliding_min(6).count(), 0);
+    }
+
+    #[test]
+    fn works_on_non_copy_items() {
+        let words = ["pear", "apple", "fig", "kiwi"].map(String::from);
+        let min: Vec<String> = words.into_iter().sliding_min(2).collect();
+        assert_eq!(min, vec!["apple", "apple", "fig"]);
+    }
+
+    #[test]
+    fn random_against_slices() {
+        let mut rng = Rng::new(29);
+        for _ in 0..200 {
+            let len = rng.below(30) as usize + 1;
+            let k = rng.below(len as u64) as usize + 1;
+            let nums = rng.vec(len, -15, 15);
+            let max: Vec<i64> = nums.iter().copied().sliding_max(k).collect();
+            let min: Vec<i64> = nums.iter().copied().sliding_min(k).collect();
+            assert_eq!(max, sliding_window_max(&nums, k));
+            assert_eq!(min, sliding_window_min(&nums, k));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+pub mod aggregate;
+pub mod heap;
+pub mod iter;
+pub mod median;
+pub mod monotonic;
+pub mod multiset;
+pub mod permutation;
+pub mod sort;
+pub mod time_window;
+pub mod variable_window;
+
+use aggregate::{Max, SlidingWindowAggregator};
+use multiset::OrderedMultiset;
+
+/// Maximum of every window of `k` consecutive elements of `nums`.
+///
+/// The window is a [`SlidingWindowAggregator`] over the [`Max`] monoid, so
+/// every step is amortized O(1).
+pub fn max_sliding_window(nums: Vec<i32>, k: i32) -> Vec<i32> {
+    let k = k as usize;
+    let mut result: Vec<i32> = Vec::new();
+    if k == 0 || k > nums.len() {
+        return result;
+    }
+    let mut window = SlidingWindowAggregator::new(Max::new());
+
+    for el in nums {
+        window.push_back(Some(el));
+        if window.len() > k {
+            window.pop_front();
+        }
+        if window.len() == k {
+            result.push(window.query().expect("window is not empty"));
+        }
+    }
+
+    result
+}
+
+/// Same as [`max_sliding_window`], keeping the window sorted in an
+/// [`OrderedMultiset`], in O(n log k).
+pub fn max_sliding_window_sorted(nums: Vec<i32>, k: i32) -> Vec<i32> {
+    let k = k as usize;
+    let mut result: Vec<i32> = Vec::new();
+    if k == 0 || k > nums.len() {
+        return result;
+    }
+
+    let mut window_sorted: OrderedMultiset<i32> = nums[0..k].iter().copied().collect();
+    for i in 0..=nums.len() - k {
+        result.push(*window_sorted.last().expect("window is not empty"));
+        if i + k == nums.len() {
+            break;
+        }
+        window_sorted.remove_one(&nums[i]);
+        window_sorted.insert(nums[i + k]);
+    }
+
+    result
+}
+
+/// Returns `a` reordered so that `result[i] = a[perm[i]]`.
+pub fn permutate(a: &[i32], perm: &[usize]) -> Vec<i32> {
+    let mut result: Vec<i32> = vec![0; a.len()];
+    for i in 0..a.len() {
+        result[i] = a[perm[i]];
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leetcode_example() {
+        let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(max_sliding_window(nums.clone(), 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(max_sliding_window_sorted(nums, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(max_sliding_window(vec![1], 1), vec![1]);
+    }
+
+    #[test]
+    fn aggregator_and_multiset_agree() {
+        let mut rng = test_rng::Rng::new(7);
+        for _ in 0..200 {
+            let len = rng.below(30) as usize + 1;
+            let nums: Vec<i32> = rng.vec(len, -10, 10).iter().map(|x| *x as i32).collect();
+            let k = rng.below(len as u64) as i32 + 1;
+            assert_eq!(
+                max_sliding_window(nums.clone(), k),
+                max_sliding_window_sorted(nums, k)
+            );
+        }
+    }
+}
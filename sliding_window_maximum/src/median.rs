@@ -0,0 +1,225 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::heap::{Handle, IndexedHeap};
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Low(Handle),
+    High(Handle),
+}
+
+/// FIFO queue answering "`rank`-th smallest queued element" (0-based) in
+/// O(log n) per operation.
+///
+/// The `rank + 1` smallest elements live in the max-heap `low`, the others in
+/// the min-heap `high`. Keys are tagged with their push sequence number so an
+/// element moved between the heaps can find its slot in `sides` again. The
+/// heaps reuse the slots of removed elements, so memory stays O(n) however
+/// long the stream.
+#[derive(Debug, Clone)]
+pub struct KthWindow<T> {
+    rank: usize,
+    low: IndexedHeap<(T, usize)>,
+    high: IndexedHeap<Reverse<(T, usize)>>,
+    sides: VecDeque<Side>,
+    popped: usize,
+}
+
+impl<T: Ord> KthWindow<T> {
+    pub fn new(rank: usize) -> Self {
+        Self {
+            rank,
+            low: IndexedHeap::new(),
+            high: IndexedHeap::new(),
+            sides: VecDeque::new(),
+            popped: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sides.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sides.is_empty()
+    }
+
+    pub fn push_back(&mut self, el: T) {
+        let seq = self.popped + self.sides.len();
+        let side = match self.low.peek() {
+            Some((_, (max, _))) if el <= *max => Side::Low(self.low.push((el, seq))),
+            _ => Side::High(self.high.push(Reverse((el, seq)))),
+        };
+        self.sides.push_back(side);
+        self.rebalance();
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let el = match self.sides.pop_front()? {
+            Side::Low(h) => self.low.remove(h).map(|(el, _)| el),
+            Side::High(h) => self.high.remove(h).map(|Reverse((el, _))| el),
+        };
+        self.popped += 1;
+        self.rebalance();
+        el
+    }
+
+    /// The `rank`-th smallest queued element, if there are more than `rank`.
+    pub fn get(&self) -> Option<&T> {
+        if self.low.len() <= self.rank {
+            return None;
+        }
+        self.low.peek().map(|(_, (el, _))| el)
+    }
+
+    /// The `rank + 1`-th smallest queued element, if there is one.
+    pub fn successor(&self) -> Option<&T> {
+        self.high.peek().map(|(_, Reverse((el, _)))| el)
+    }
+
+    fn rebalance(&mut self) {
+        while self.low.len() > self.rank + 1 {
+            let (_, (el, seq)) = self.low.pop().expect("low is not empty");
+            let h = self.high.push(Reverse((el, seq)));
+            self.sides[seq - self.popped] = Side::High(h);
+        }
+        while self.low.len() < self.rank + 1 {
+            let Some((_, Reverse((el, seq)))) = self.high.pop() else {
+                break;
+            };
+            let h = self.low.push((el, seq));
+            self.sides[seq - self.popped] = Side::Low(h);
+        }
+    }
+}
+
+/// `rank`-th smallest (0-based) element of every window of `k` consecutive
+/// elements, in O(n log k). Empty if `rank >= k`.
+pub fn sliding_window_kth<T: Ord + Clone>(nums: &[T], k: usize, rank: usize) -> Vec<T> {
+    let mut result = Vec::new();
+    if rank >= k || k > nums.len() {
+        return result;
+    }
+    let mut window = KthWindow::new(rank);
+
+    for el in nums {
+        window.push_back(el.clone());
+        if window.len() > k {
+            window.pop_front();
+        }
+        if window.len() == k {
+            result.push(
+                window
+                    .get()
+                    .expect("window has more than rank elements")
+                    .clone(),
+            );
+        }
+    }
+
+    result
+}
+
+/// Nearest-rank `p`-th percentile (`0.0..=100.0`) of every window of `k`
+/// consecutive elements.
+pub fn sliding_window_percentile<T: Ord + Clone>(nums: &[T], k: usize, p: f64) -> Vec<T> {
+    let rank = ((p / 100.0 * k as f64).ceil() as usize).clamp(1, k.max(1)) - 1;
+    sliding_window_kth(nums, k, rank)
+}
+
+/// Median of every window of `k` consecutive elements; for even `k` it is the
+/// mean of the two middle elements.
+pub fn sliding_window_median(nums: &[i32], k: usize) -> Vec<f64> {
+    let mut result = Vec::new();
+    if k == 0 || k > nums.len() {
+        return result;
+    }
+    let mut window = KthWindow::new((k - 1) / 2);
+
+    for el in nums {
+        window.push_back(*el);
+        if window.len() > k {
+            window.pop_front();
+        }
+        if window.len() == k {
+            let lower = *window.get().expect("window is full") as f64;
+            if k % 2 == 1 {
+                result.push(lower);
+            } else {
+                let upper = *window.successor().expect("window is full") as f64;
+                result.push((lower + upper) / 2.0);
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example() {
+        let nums = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(
+            sliding_window_median(&nums, 3),
+            vec![1.0, -1.0, -1.0, 3.0, 5.0, 6.0]
+        );
+        assert_eq!(
+            sliding_window_median(&[1, 2, 3, 4, 2, 3, 1, 4, 2], 4),
+            vec![2.5, 2.5, 3.0, 2.5, 2.5, 2.5]
+        );
+        assert_eq!(
+            sliding_window_median(&[i32::MAX, i32::MAX], 2),
+            vec![i32::MAX as f64]
+        );
+    }
+
+    #[test]
+    fn percentiles() {
+        let nums = [5, 1, 4, 2, 3];
+        assert_eq!(sliding_window_percentile(&nums, 5, 0.0), vec![1]);
+        assert_eq!(sliding_window_percentile(&nums, 5, 50.0), vec![3]);
+        assert_eq!(sliding_window_percentile(&nums, 5, 100.0), vec![5]);
+        assert!(sliding_window_kth(&nums, 3, 3).is_empty());
+    }
+
+    #[test]
+    fn long_stream_reuses_heap_slots() {
+        let mut window = KthWindow::new(1);
+        for el in 0..10_000 {
+            window.push_back(el % 7);
+            if window.len() > 4 {
+                window.pop_front();
+            }
+        }
+        assert_eq!(window.len(), 4);
+        let handles = window.sides.iter().map(|side| match side {
+            Side::Low(h) | Side::High(h) => h.index(),
+        });
+        assert!(handles.max().unwrap() < 6);
+    }
+
+    #[test]
+    fn random_against_sorting() {
+        let mut rng = Rng::new(30);
+        for _ in 0..200 {
+            let len = rng.below(30) as usize + 1;
+            let k = rng.below(len as u64) as usize + 1;
+            let rank = rng.below(k as u64) as usize;
+            let nums = rng.vec(len, -8, 8);
+            let expected: Vec<i64> = nums
+                .windows(k)
+                .map(|w| {
+                    let mut w = w.to_vec();
+                    w.sort();
+                    w[rank]
+                })
+                .collect();
+            assert_eq!(sliding_window_kth(&nums, k, rank), expected);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Extremum {
+    Max,
+    Min,
+}
+
+/// FIFO queue that answers "maximum (or minimum) of the queued elements" in
+/// amortized O(1).
+///
+/// Only the elements that can still become the answer are stored, tagged
+/// with their push sequence number so `pop_front` knows whether the oldest
+/// element is still in the deque.
+#[derive(Debug, Clone)]
+pub struct MonotonicDeque<T> {
+    deque: VecDeque<(usize, T)>,
+    kind: Extremum,
+    pushed: usize,
+    popped: usize,
+}
+
+impl<T: Ord> MonotonicDeque<T> {
+    /// Deque answering the maximum of the queued elements.
+    pub fn max() -> Self {
+        Self::new(Extremum::Max)
+    }
+
+    /// Deque answering the minimum of the queued elements.
+    pub fn min() -> Self {
+        Self::new(Extremum::Min)
+    }
+
+    fn new(kind: Extremum) -> Self {
+        Self {
+            deque: VecDeque::new(),
+            kind,
+            pushed: 0,
+            popped: 0,
+        }
+    }
+
+    /// Number of elements in the queue, including the ones not stored.
+    pub fn len(&self) -> usize {
+        self.pushed - self.popped
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_back(&mut self, el: T) {
+        while let Some((_, back)) = self.deque.back() {
+            if !self.dominates(&el, back) {
+                break;
+            }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.pushed, el));
+        self.pushed += 1;
+    }
+
+    /// Removes the oldest element of the queue, returning it if it was still
+    /// stored.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.popped += 1;
+        match self.deque.front() {
+            Some((seq, _)) if *seq < self.popped => self.deque.pop_front().map(|(_, el)| el),
+            _ => None,
+        }
+    }
+
+    /// Maximum (or minimum) of the queued elements.
+    pub fn best(&self) -> Option<&T> {
+        self.deque.front().map(|(_, el)| el)
+    }
+
+    pub fn clear(&mut self) {
+        self.deque.clear();
+        self.popped = self.pushed;
+    }
+
+    fn dominates(&self, new: &T, old: &T) -> bool {
+        match self.kind {
+            Extremum::Max => new >= old,
+            Extremum::Min => new <= old,
+        }
+    }
+}
+
+/// Maximum of every window of `k` consecutive elements, in O(n).
+pub fn sliding_window_max<T: Ord + Clone>(nums: &[T], k: usize) -> Vec<T> {
+    sliding_window(nums, k, MonotonicDeque::max())
+}
+
+/// Minimum of every window of `k` consecutive elements, in O(n).
+pub fn sliding_window_min<T: Ord + Clone>(nums: &[T], k: usize) -> Vec<T> {
+    sliding_window(nums, k, MonotonicDeque::min())
+}
+
+/// `(min, max)` of every window of `k` consecutive elements, in O(n).
+pub fn sliding_window_min_max<T: Ord + Clone>(nums: &[T], k: usize) -> Vec<(T, T)> {
+    sliding_window_min(nums, k)
+        .into_iter()
+        .zip(sliding_window_max(nums, k))
+        .collect()
+}
+
+/// Maximum of every `a x b` sub-rectangle of `grid` (`a` rows, `b` columns),
+/// in O(nm). `result[i][j]` is the maximum of the rectangle whose top-left
+/// corner is `grid[i][j]`.
+pub fn sliding_window_max_2d<T: Ord + Clone>(grid: &[Vec<T>], a: usize, b: usize) -> Vec<Vec<T>> {
+    sliding_window_2d(grid, a, b, sliding_window_max)
+}
+
+/// Minimum of every `a x b` sub-rectangle of `grid`, see
+/// [`sliding_window_max_2d`].
+pub fn sliding_window_min_2d<T: Ord + Clone>(grid: &[Vec<T>], a: usize, b: usize) -> Vec<Vec<T>> {
+    sliding_window_2d(grid, a, b, sliding_window_min)
+}
+
+/// Applies the 1D `window` along the rows, then along the columns of the
+/// result.
+fn sliding_window_2d<T: Ord + Clone>(
+    grid: &[Vec<T>],
+    a: usize,
+    b: usize,
+    window: fn(&[T], usize) -> Vec<T>,
+) -> Vec<Vec<T>> {
+    if a == 0 || a > grid.len() {
+        return Vec::new();
+    }
+    let rows: Vec<Vec<T>> = grid.iter().map(|row| window(row, b)).collect();
+    let width = rows[0].len();
+    assert!(
+        rows.iter().all(|row| row.len() == width),
+        "grid rows must have the same length"
+    );
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut result = vec![Vec::with_capacity(width); grid.len() - a + 1];
+    for j in 0..width {
+        let column: Vec<T> = rows.iter().map(|row| row[j].clone()).collect();
+        for (i, el) in window(&column, a).into_iter().enumerate() {
+            result[i].push(el);
+        }
+    }
+
+    result
+}
+
+fn sliding_window<T: Ord + Clone>(nums: &[T], k: usize, mut window: MonotonicDeque<T>) -> Vec<T> {
+    let mut result = Vec::new();
+    if k == 0 || k > nums.len() {
+        return result;
+    }
+    result.reserve(nums.len() - k + 1);
+
+    for el in nums {
+        window.push_back(el.clone());
+        if window.len() > k {
+            window.pop_front();
+        }
+        if window.len() == k {
+            result.push(window.best().expect("window is not empty").clone());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::max_sliding_window;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example() {
+        let nums = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(sliding_window_max(&nums, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(sliding_window_min(&nums, 3), vec![-1, -3, -3, -3, 3, 3]);
+        assert_eq!(sliding_window_min_max(&nums, 8), vec![(-3, 7)]);
+        assert!(sliding_window_max(&nums, 9).is_empty());
+        assert!(sliding_window_max(&nums, 0).is_empty());
+    }
+
+    #[test]
+    fn deque_pop_front_returns_stored_elements() {
+        let mut deque = MonotonicDeque::max();
+        for el in [2, 5, 1] {
+            deque.push_back(el);
+        }
+        assert_eq!(deque.best(), Some(&5));
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_front(), Some(5));
+        assert_eq!(deque.best(), Some(&1));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), None);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn rectangles_2d() {
+        let grid = vec![vec![1, 5, 2, 0], vec![3, 0, 4, 8], vec![7, 1, 1, 2]];
+        assert_eq!(
+            sliding_window_max_2d(&grid, 2, 3),
+            vec![vec![5, 8], vec![7, 8]]
+        );
+        assert_eq!(sliding_window_min_2d(&grid, 3, 1), vec![vec![1, 0, 1, 0]]);
+        assert_eq!(sliding_window_max_2d(&grid, 1, 1), grid);
+        assert!(sliding_window_max_2d(&grid, 4, 1).is_empty());
+        assert!(sliding_window_max_2d(&grid, 1, 5).is_empty());
+    }
+
+    #[test]
+    fn random_2d_against_brute_force() {
+        let mut rng = Rng::new(32);
+        for _ in 0..100 {
+            let (n, m) = (rng.below(8) as usize + 1, rng.below(8) as usize + 1);
+            let (a, b) = (
+                rng.below(n as u64) as usize + 1,
+                rng.below(m as u64) as usize + 1,
+            );
+            let grid: Vec<Vec<i64>> = (0..n).map(|_| rng.vec(m, -20, 20)).collect();
+            let expected: Vec<Vec<i64>> = (0..=n - a)
+                .map(|i| {
+                    (0..=m - b)
+                        .map(|j| {
+                            grid[i..i + a]
+                                .iter()
+                                .flat_map(|r| &r[j..j + b])
+                                .max()
+                                .copied()
+                                .unwrap()
+                        })
+                        .collect()
+                })
+                .collect();
+            assert_eq!(sliding_window_max_2d(&grid, a, b), expected);
+
+            let row: Vec<i32> = grid[0].iter().map(|x| *x as i32).collect();
+            assert_eq!(
+                sliding_window_max_2d(std::slice::from_ref(&row), 1, b),
+                vec![max_sliding_window(row, b as i32)]
+            );
+        }
+    }
+
+    #[test]
+    fn random_against_max_sliding_window() {
+        let mut rng = Rng::new(27);
+        for _ in 0..300 {
+            let len = rng.below(40) as usize + 1;
+            let nums: Vec<i32> = rng.vec(len, -20, 20).iter().map(|x| *x as i32).collect();
+            let k = rng.below(len as u64) as usize + 1;
+            let negated: Vec<i32> = nums.iter().map(|x| -x).collect();
+            let expected_min: Vec<i32> = max_sliding_window(negated, k as i32)
+                .iter()
+                .map(|x| -x)
+                .collect();
+
+            assert_eq!(
+                sliding_window_max(&nums, k),
+                max_sliding_window(nums.clone(), k as i32)
+            );
+            assert_eq!(sliding_window_min(&nums, k), expected_min);
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    key: T,
+    count: usize,
+    size: usize,
+    priority: u64,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Sorted multiset with O(log n) expected time for every operation.
+///
+/// It is a treap keeping one node per distinct key, with `count` copies of
+/// it, and `size` the number of elements in the node's subtree. Nodes live in
+/// `nodes`; the slots of removed ones are reused through `free`.
+#[derive(Debug, Clone)]
+pub struct OrderedMultiset<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    seed: u64,
+}
+
+impl<T: Ord> OrderedMultiset<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            seed: 0x2545_F491_4F6C_DD1D,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn insert(&mut self, key: T) {
+        self.root = Some(self.insert_at(self.root, key));
+    }
+
+    /// Removes one copy of `key`, returning whether there was one.
+    pub fn remove_one(&mut self, key: &T) -> bool {
+        let (root, removed) = self.remove_at(self.root, key);
+        self.root = root;
+        removed
+    }
+
+    /// Number of copies of `key`.
+    pub fn count(&self, key: &T) -> usize {
+        let mut cur = self.root;
+        while let Some(n) = cur {
+            match key.cmp(&self.nodes[n].key) {
+                Ordering::Less => cur = self.nodes[n].left,
+                Ordering::Greater => cur = self.nodes[n].right,
+                Ordering::Equal => return self.nodes[n].count,
+            }
+        }
+        0
+    }
+
+    pub fn contains(&self, key: &T) -> bool {
+        self.count(key) > 0
+    }
+
+    /// Number of elements strictly smaller than `key`.
+    pub fn rank(&self, key: &T) -> usize {
+        let mut rank = 0;
+        let mut cur = self.root;
+        while let Some(n) = cur {
+            let node = &self.nodes[n];
+            match key.cmp(&node.key) {
+                Ordering::Less => cur = node.left,
+                Ordering::Greater => {
+                    rank += self.size(node.left) + node.count;
+                    cur = node.right;
+                }
+                Ordering::Equal => return rank + self.size(node.left),
+            }
+        }
+        rank
+    }
+
+    /// The `k`-th smallest element (0-based), counting copies.
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut cur = self.root;
+        while let Some(n) = cur {
+            let node = &self.nodes[n];
+            let left = self.size(node.left);
+            if k < left {
+                cur = node.left;
+            } else if k < left + node.count {
+                return Some(&node.key);
+            } else {
+                k -= left + node.count;
+                cur = node.right;
+            }
+        }
+        None
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.select(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.select(self.len().checked_sub(1)?)
+    }
+
+    /// Smallest element greater than or equal to `key`.
+    pub fn lower_bound(&self, key: &T) -> Option<&T> {
+        self.select(self.rank(key))
+    }
+
+    /// Smallest element strictly greater than `key`.
+    pub fn upper_bound(&self, key: &T) -> Option<&T> {
+        self.select(self.rank(key) + self.count(key))
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |n| self.nodes[n].size)
+    }
+
+    fn update(&mut self, n: usize) {
+        let node = &self.nodes[n];
+        self.nodes[n].size = node.count + self.size(node.left) + self.size(node.right);
+    }
+
+    fn new_node(&mut self, key: T) -> usize {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        let node = Node {
+            key,
+            count: 1,
+            size: 1,
+            priority: self.seed,
+            left: None,
+            right: None,
+        };
+        match self.free.pop() {
+            Some(n) => {
+                self.nodes[n] = node;
+                n
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn rotate_right(&mut self, n: usize) -> usize {
+        let l = self.nodes[n].left.expect("rotated node has a left child");
+        self.nodes[n].left = self.nodes[l].right;
+        self.nodes[l].right = Some(n);
+        self.update(n);
+        self.update(l);
+        l
+    }
+
+    fn rotate_left(&mut self, n: usize) -> usize {
+        let r = self.nodes[n].right.expect("rotated node has a right child");
+        self.nodes[n].right = self.nodes[r].left;
+        self.nodes[r].left = Some(n);
+        self.update(n);
+        self.update(r);
+        r
+    }
+
+    fn insert_at(&mut self, node: Option<usize>, key: T) -> usize {
+        let Some(n) = node else {
+            return self.new_node(key);
+        };
+        let mut root = n;
+        match key.cmp(&self.nodes[n].key) {
+            Ordering::Equal => self.nodes[n].count += 1,
+            Ordering::Less => {
+                let l = self.insert_at(self.nodes[n].left, key);
+                self.nodes[n].left = Some(l);
+                if self.nodes[l].priority > self.nodes[n].priority {
+                    root = self.rotate_right(n);
+                }
+            }
+            Ordering::Greater => {
+                let r = self.insert_at(self.nodes[n].right, key);
+                self.nodes[n].right = Some(r);
+                if self.nodes[r].priority > self.nodes[n].priority {
+                    root = self.rotate_left(n);
+                }
+            }
+        }
+        self.update(n);
+        self.update(root);
+        root
+    }
+
+    fn remove_at(&mut self, node: Option<usize>, key: &T) -> (Option<usize>, bool) {
+        let Some(n) = node else {
+            return (None, false);
+        };
+        let removed = match key.cmp(&self.nodes[n].key) {
+            Ordering::Less => {
+                let (l, removed) = self.remove_at(self.nodes[n].left, key);
+                self.nodes[n].left = l;
+                removed
+            }
+            Ordering::Greater => {
+                let (r, removed) = self.remove_at(self.nodes[n].right, key);
+                self.nodes[n].right = r;
+                removed
+            }
+            Ordering::Equal if self.nodes[n].count > 1 => {
+                self.nodes[n].count -= 1;
+                true
+            }
+            Ordering::Equal => {
+                self.free.push(n);
+                let merged = self.merge(self.nodes[n].left, self.nodes[n].right);
+                return (merged, true);
+            }
+        };
+        self.update(n);
+        (Some(n), removed)
+    }
+
+    fn merge(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        let (Some(x), Some(y)) = (a, b) else {
+            return a.or(b);
+        };
+        if self.nodes[x].priority > self.nodes[y].priority {
+            let r = self.merge(self.nodes[x].right, Some(y));
+            self.nodes[x].right = r;
+            self.update(x);
+            Some(x)
+        } else {
+            let l = self.merge(Some(x), self.nodes[y].left);
+            self.nodes[y].left = l;
+            self.update(y);
+            Some(y)
+        }
+    }
+}
+
+impl<T: Ord> Default for OrderedMultiset<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderedMultiset<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for el in iter {
+            set.insert(el);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn queries_with_duplicates() {
+        let mut set: OrderedMultiset<i32> = [5, 1, 3, 3, 9, 3].into_iter().collect();
+        assert_eq!(set.len(), 6);
+        assert_eq!(set.count(&3), 3);
+        assert_eq!(set.rank(&3), 1);
+        assert_eq!(set.rank(&4), 4);
+        assert_eq!(set.select(3), Some(&3));
+        assert_eq!(set.select(4), Some(&5));
+        assert_eq!(set.select(6), None);
+        assert_eq!(set.lower_bound(&4), Some(&5));
+        assert_eq!(set.upper_bound(&3), Some(&5));
+        assert_eq!(set.upper_bound(&9), None);
+
+        assert!(set.remove_one(&3));
+        assert!(!set.remove_one(&4));
+        assert_eq!(set.count(&3), 2);
+        assert_eq!((set.first(), set.last()), (Some(&1), Some(&9)));
+    }
+
+    #[test]
+    fn random_against_sorted_vec() {
+        let mut rng = Rng::new(35);
+        let mut set = OrderedMultiset::new();
+        let mut sorted: Vec<i64> = Vec::new();
+        for _ in 0..3000 {
+            let x = rng.range(-30, 30);
+            if rng.below(3) == 0 {
+                let pos = sorted.iter().position(|y| *y == x);
+                assert_eq!(set.remove_one(&x), pos.is_some());
+                if let Some(pos) = pos {
+                    sorted.remove(pos);
+                }
+            } else {
+                set.insert(x);
+                let pos = sorted.partition_point(|y| *y < x);
+                sorted.insert(pos, x);
+            }
+
+            let rank = sorted.partition_point(|y| *y < x);
+            let upper = sorted.partition_point(|y| *y <= x);
+            assert_eq!(set.len(), sorted.len());
+            assert_eq!(set.rank(&x), rank);
+            assert_eq!(set.count(&x), upper - rank);
+            assert_eq!(set.lower_bound(&x), sorted.get(rank));
+            assert_eq!(set.upper_bound(&x), sorted.get(upper));
+            if !sorted.is_empty() {
+                let k = rng.below(sorted.len() as u64) as usize;
+                assert_eq!(set.select(k), Some(&sorted[k]));
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::sort::Sorted;
+
+/// Permutation of `0..n`, stored as the index vector used by
+/// [`crate::permutate`] and [`Sorted::permutation`]: applying it to `a` gives
+/// `result[i] = a[perm[i]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Permutation {
+    perm: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn identity(n: usize) -> Self {
+        Self {
+            perm: (0..n).collect(),
+        }
+    }
+
+    /// `None` if `perm` is not a permutation of `0..perm.len()`.
+    pub fn from_vec(perm: Vec<usize>) -> Option<Self> {
+        let mut seen = vec![false; perm.len()];
+        for i in &perm {
+            if *i >= perm.len() || seen[*i] {
+                return None;
+            }
+            seen[*i] = true;
+        }
+        Some(Self { perm })
+    }
+
+    pub fn len(&self) -> usize {
+        self.perm.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.perm.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[usize] {
+        &self.perm
+    }
+
+    pub fn into_vec(self) -> Vec<usize> {
+        self.perm
+    }
+
+    /// Returns `a` reordered so that `result[i] = a[perm[i]]`.
+    pub fn apply<T: Clone>(&self, a: &[T]) -> Vec<T> {
+        assert_eq!(a.len(), self.len(), "permutation and slice lengths differ");
+        self.perm.iter().map(|i| a[*i].clone()).collect()
+    }
+
+    /// Same as [`Permutation::apply`], in place, by swapping along each cycle.
+    pub fn apply_in_place<T>(&self, a: &mut [T]) {
+        assert_eq!(a.len(), self.len(), "permutation and slice lengths differ");
+        let mut done = vec![false; a.len()];
+        for start in 0..a.len() {
+            let mut j = start;
+            while !done[j] {
+                done[j] = true;
+                let next = self.perm[j];
+                if next == start {
+                    break;
+                }
+                a.swap(j, next);
+                j = next;
+            }
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inv = vec![0; self.len()];
+        for (i, p) in self.perm.iter().enumerate() {
+            inv[*p] = i;
+        }
+        Self { perm: inv }
+    }
+
+    /// Permutation equivalent to applying `other`, then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len(), "permutation lengths differ");
+        Self {
+            perm: self.perm.iter().map(|i| other.perm[*i]).collect(),
+        }
+    }
+
+    /// Cycles of `i -> perm[i]`, each starting from its smallest element,
+    /// fixed points included.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut done = vec![false; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            if done[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut j = start;
+            while !done[j] {
+                done[j] = true;
+                cycle.push(j);
+                j = self.perm[j];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// `1` for even permutations, `-1` for odd ones.
+    pub fn sign(&self) -> i8 {
+        if self.is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_even(&self) -> bool {
+        (self.len() - self.cycles().len()).is_multiple_of(2)
+    }
+
+    /// `self` composed with itself `k` times, in O(n); negative powers use
+    /// the inverse.
+    pub fn pow(&self, k: i64) -> Self {
+        let mut perm = vec![0; self.len()];
+        for cycle in self.cycles() {
+            let len = cycle.len() as i64;
+            let shift = k.rem_euclid(len) as usize;
+            for (j, el) in cycle.iter().enumerate() {
+                perm[*el] = cycle[(j + shift) % cycle.len()];
+            }
+        }
+        Self { perm }
+    }
+
+    /// Moves to the lexicographically next permutation, returning `false`
+    /// (and leaving `self` unchanged) if it is the last one.
+    pub fn next_permutation(&mut self) -> bool {
+        let p = &mut self.perm;
+        let Some(i) = (1..p.len()).rev().find(|i| p[i - 1] < p[*i]) else {
+            return false;
+        };
+        let j = (i..p.len())
+            .rev()
+            .find(|j| p[*j] > p[i - 1])
+            .expect("p[i] > p[i - 1]");
+        p.swap(i - 1, j);
+        p[i..].reverse();
+        true
+    }
+
+    /// Moves to the lexicographically previous permutation, returning `false`
+    /// (and leaving `self` unchanged) if it is the first one.
+    pub fn prev_permutation(&mut self) -> bool {
+        let p = &mut self.perm;
+        let Some(i) = (1..p.len()).rev().find(|i| p[i - 1] > p[*i]) else {
+            return false;
+        };
+        let j = (i..p.len())
+            .rev()
+            .find(|j| p[*j] < p[i - 1])
+            .expect("p[i] < p[i - 1]");
+        p.swap(i - 1, j);
+        p[i..].reverse();
+        true
+    }
+
+    /// Position of `self` among the permutations of `0..n` in lexicographic
+    /// order, in O(n^2). Fits in a `u128` for `n <= 34`.
+    pub fn rank(&self) -> u128 {
+        let n = self.len();
+        assert!(
+            n <= 34,
+            "rank of a permutation of more than 34 elements overflows"
+        );
+        let mut rank = 0;
+        for i in 0..n {
+            let smaller = self.perm[i + 1..]
+                .iter()
+                .filter(|x| **x < self.perm[i])
+                .count();
+            rank = rank * (n - i) as u128 + smaller as u128;
+        }
+        rank
+    }
+
+    /// Inverse of [`Permutation::rank`]: the `rank`-th permutation of `0..n`.
+    pub fn unrank(n: usize, mut rank: u128) -> Self {
+        assert!(
+            n <= 34,
+            "rank of a permutation of more than 34 elements overflows"
+        );
+        let mut digits = vec![0; n];
+        for i in (0..n).rev() {
+            let base = (n - i) as u128;
+            digits[i] = (rank % base) as usize;
+            rank /= base;
+        }
+        assert_eq!(rank, 0, "rank out of range");
+
+        let mut left: Vec<usize> = (0..n).collect();
+        Self {
+            perm: digits.into_iter().map(|d| left.remove(d)).collect(),
+        }
+    }
+}
+
+impl<T> From<&Sorted<T>> for Permutation {
+    fn from(sorted: &Sorted<T>) -> Self {
+        Self {
+            perm: sorted.permutation.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutate;
+    use crate::sort::merge_sort;
+    use test_rng::Rng;
+
+    #[test]
+    fn sort_permutation_applies_like_permutate() {
+        let nums = vec![1, 3, -1, -3, 5, 3, 6, 7];
+        let sorted = merge_sort(&nums);
+        let perm = Permutation::from(&sorted);
+        assert_eq!(perm.apply(&nums), sorted.sorted);
+        assert_eq!(perm.apply(&nums), permutate(&nums, perm.as_slice()));
+
+        let mut in_place = nums.clone();
+        perm.apply_in_place(&mut in_place);
+        assert_eq!(in_place, sorted.sorted);
+        assert_eq!(perm.inverse().apply(&sorted.sorted), nums);
+    }
+
+    #[test]
+    fn cycles_sign_and_pow() {
+        let perm = Permutation::from_vec(vec![1, 2, 0, 4, 3, 5]).unwrap();
+        assert_eq!(perm.cycles(), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(perm.sign(), -1);
+        assert_eq!(perm.pow(6), Permutation::identity(6));
+        assert_eq!(perm.pow(-1), perm.inverse());
+        assert_eq!(perm.pow(2), perm.compose(&perm));
+        assert!(Permutation::from_vec(vec![0, 0]).is_none());
+        assert!(Permutation::from_vec(vec![2, 0]).is_none());
+    }
+
+    #[test]
+    fn lexicographic_walk_matches_rank() {
+        let mut perm = Permutation::identity(4);
+        let mut rank = 0;
+        loop {
+            assert_eq!(perm.rank(), rank);
+            assert_eq!(Permutation::unrank(4, rank), perm);
+            if !perm.next_permutation() {
+                break;
+            }
+            rank += 1;
+        }
+        assert_eq!(rank, 23);
+        assert_eq!(perm.as_slice(), &[3, 2, 1, 0]);
+        assert!(perm.prev_permutation());
+        assert_eq!(perm.rank(), 22);
+    }
+
+    #[test]
+    fn random_compose_and_in_place() {
+        let mut rng = Rng::new(34);
+        for _ in 0..100 {
+            let len = rng.below(12) as usize + 1;
+            let p = Permutation::unrank(
+                len,
+                rng.next_u64() as u128 % (1..=len as u128).product::<u128>(),
+            );
+            let q = Permutation::from(&merge_sort(&rng.vec(len, 0, 100)));
+            let a = rng.vec(len, -50, 50);
+
+            assert_eq!(p.compose(&q).apply(&a), p.apply(&q.apply(&a)));
+            let mut b = a.clone();
+            p.apply_in_place(&mut b);
+            assert_eq!(b, p.apply(&a));
+            assert_eq!(p.compose(&p.inverse()), Permutation::identity(len));
+            assert_eq!(p.compose(&q).sign(), p.sign() * q.sign());
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+
+/// Output of a [`MergeSort`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sorted<T> {
+    pub sorted: Vec<T>,
+    /// `sorted[i]` was at position `permutation[i]` of the input.
+    pub permutation: Vec<usize>,
+    /// Number of pairs `i < j` with `input[i] > input[j]`.
+    pub inversions: u64,
+}
+
+/// Stable merge sort returning the sorting permutation and the number of
+/// inversions of the input.
+///
+/// Only indices are moved around while sorting, so `T` is cloned once per
+/// element. The default top-down sort recurses O(log n) deep; `bottom_up`
+/// merges runs of doubling width iteratively instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeSort {
+    bottom_up: bool,
+}
+
+impl MergeSort {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts without recursion.
+    pub fn bottom_up(mut self) -> Self {
+        self.bottom_up = true;
+        self
+    }
+
+    pub fn sort<T: Ord + Clone>(&self, arr: &[T]) -> Sorted<T> {
+        self.sort_by(arr, T::cmp)
+    }
+
+    pub fn sort_by_key<T, K, F>(&self, arr: &[T], mut key: F) -> Sorted<T>
+    where
+        T: Clone,
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(arr, |a, b| key(a).cmp(&key(b)))
+    }
+
+    pub fn sort_by<T, F>(&self, arr: &[T], mut cmp: F) -> Sorted<T>
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut a: Vec<usize> = (0..arr.len()).collect();
+        let mut b = a.clone();
+        let mut less_eq = |i: &usize, j: &usize| cmp(&arr[*i], &arr[*j]) != Ordering::Greater;
+        let mut inversions = 0;
+
+        let permutation = if self.bottom_up {
+            let mut width = 1;
+            while width < arr.len() {
+                for begin in (0..arr.len()).step_by(2 * width) {
+                    let middle = (begin + width).min(arr.len());
+                    let end = (begin + 2 * width).min(arr.len());
+                    inversions += merge(&a, begin, middle, end, &mut b, &mut less_eq);
+                }
+                std::mem::swap(&mut a, &mut b);
+                width *= 2;
+            }
+            a
+        } else {
+            split(&mut a, 0, arr.len(), &mut b, &mut less_eq, &mut inversions);
+            b
+        };
+
+        Sorted {
+            sorted: permutation.iter().map(|i| arr[*i].clone()).collect(),
+            permutation,
+            inversions,
+        }
+    }
+}
+
+/// Stable top-down merge sort of `arr`, see [`MergeSort`].
+pub fn merge_sort<T: Ord + Clone>(arr: &[T]) -> Sorted<T> {
+    MergeSort::new().sort(arr)
+}
+
+/// Number of inversions of `arr`, in O(n log n).
+pub fn count_inversions<T: Ord + Clone>(arr: &[T]) -> u64 {
+    MergeSort::new().bottom_up().sort(arr).inversions
+}
+
+/// Merges the sorted runs `a[begin..middle]` and `a[middle..end]` into
+/// `b[begin..end]`, returning the inversions between the two runs.
+fn merge<F>(
+    a: &[usize],
+    begin: usize,
+    middle: usize,
+    end: usize,
+    b: &mut [usize],
+    less_eq: &mut F,
+) -> u64
+where
+    F: FnMut(&usize, &usize) -> bool,
+{
+    let mut i = begin;
+    let mut j = middle;
+    let mut inversions = 0;
+    for slot in b.iter_mut().take(end).skip(begin) {
+        if i < middle && (j >= end || less_eq(&a[i], &a[j])) {
+            *slot = a[i];
+            i += 1;
+        } else {
+            *slot = a[j];
+            j += 1;
+            inversions += (middle - i) as u64;
+        }
+    }
+    inversions
+}
+
+/// Sorts `b[begin..end]` into itself, using `a` (holding the same elements)
+/// as scratch space.
+fn split<F>(
+    a: &mut [usize],
+    begin: usize,
+    end: usize,
+    b: &mut [usize],
+    less_eq: &mut F,
+    inversions: &mut u64,
+) where
+    F: FnMut(&usize, &usize) -> bool,
+{
+    if end - begin <= 1 {
+        return;
+    }
+    let middle = (begin + end) / 2;
+    split(b, begin, middle, a, less_eq, inversions);
+    split(b, middle, end, a, less_eq, inversions);
+    *inversions += merge(a, begin, middle, end, b, less_eq);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn permutation_and_inversions() {
+        let result = merge_sort(&[1, 3, -1, -3, 5, 3]);
+        assert_eq!(result.sorted, vec![-3, -1, 1, 3, 3, 5]);
+        assert_eq!(result.permutation, vec![3, 2, 0, 1, 5, 4]);
+        assert_eq!(result.inversions, 6);
+        assert_eq!(merge_sort::<i32>(&[]).sorted, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn stable_by_key() {
+        let words = ["bb", "a", "cc", "d", "ee"];
+        let result = MergeSort::new()
+            .bottom_up()
+            .sort_by_key(&words, |w| w.len());
+        assert_eq!(result.sorted, vec!["a", "d", "bb", "cc", "ee"]);
+        assert_eq!(result.inversions, 3);
+
+        let result = MergeSort::new().sort_by(&words, |a, b| b.len().cmp(&a.len()));
+        assert_eq!(result.permutation, vec![0, 2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn random_against_std_sort() {
+        let mut rng = Rng::new(33);
+        for _ in 0..200 {
+            let len = rng.below(50) as usize;
+            let nums = rng.vec(len, -10, 10);
+            let mut expected: Vec<usize> = (0..len).collect();
+            expected.sort_by_key(|i| nums[*i]);
+            let inversions = (0..len)
+                .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+                .filter(|(i, j)| nums[*i] > nums[*j])
+                .count() as u64;
+
+            for sorter in [MergeSort::new(), MergeSort::new().bottom_up()] {
+                let result = sorter.sort(&nums);
+                assert_eq!(result.permutation, expected);
+                assert_eq!(result.inversions, inversions);
+            }
+        }
+    }
+}
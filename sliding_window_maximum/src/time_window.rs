@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::aggregate::{Max, Min, Monoid, SlidingWindowAggregator};
+
+/// When a [`TimeWindow`] produces results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmitPolicy {
+    /// One result per event at time `t`, over the events in `(t - duration, t]`.
+    PerEvent,
+    /// One result per window `[e - duration, e)`, for every `e` multiple of
+    /// `duration`.
+    Tumbling,
+    /// One result per window `[e - duration, e)`, for every `e` multiple of
+    /// `hop`.
+    Hopping { hop: u64 },
+}
+
+/// Aggregate of the events of one window, which ends at `end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowResult<V> {
+    pub end: u64,
+    pub value: V,
+}
+
+/// Sliding window over `(timestamp, value)` events that evicts by time.
+///
+/// Events may arrive out of order by up to `lateness`: they wait in `pending`
+/// until an event `lateness` newer has been seen, and are then released in
+/// timestamp order into the aggregator. Events older than the last released
+/// one are dropped and counted in `late_events`. Windows without events are
+/// never emitted, and the last window, if it would end past `u64::MAX`, is
+/// reported as ending at `u64::MAX`.
+pub struct TimeWindow<M: Monoid> {
+    window: SlidingWindowAggregator<M>,
+    timestamps: VecDeque<u64>,
+    pending: BTreeMap<(u64, usize), M::Value>,
+    duration: u64,
+    lateness: u64,
+    policy: EmitPolicy,
+    max_seen: Option<u64>,
+    released: Option<u64>,
+    next_end: Option<u64>,
+    /// `next_end` stands for an end past `u64::MAX`, so the window is only
+    /// closed by `flush`.
+    saturated: bool,
+    arrivals: usize,
+    late: usize,
+}
+
+impl<M: Monoid> TimeWindow<M> {
+    /// Window of `duration` time units, emitting per event, with no lateness.
+    pub fn new(monoid: M, duration: u64) -> Self {
+        assert!(duration > 0, "window duration must be positive");
+        Self {
+            window: SlidingWindowAggregator::new(monoid),
+            timestamps: VecDeque::new(),
+            pending: BTreeMap::new(),
+            duration,
+            lateness: 0,
+            policy: EmitPolicy::PerEvent,
+            max_seen: None,
+            released: None,
+            next_end: None,
+            saturated: false,
+            arrivals: 0,
+            late: 0,
+        }
+    }
+
+    /// Accepts events up to `lateness` time units older than the newest one.
+    pub fn lateness(mut self, lateness: u64) -> Self {
+        self.lateness = lateness;
+        self
+    }
+
+    pub fn policy(mut self, policy: EmitPolicy) -> Self {
+        if let EmitPolicy::Hopping { hop } = policy {
+            assert!(hop > 0, "hop must be positive");
+        }
+        self.policy = policy;
+        self
+    }
+
+    /// Number of events dropped for arriving too late.
+    pub fn late_events(&self) -> usize {
+        self.late
+    }
+
+    /// Adds an event, returning the results it made final.
+    pub fn push(&mut self, timestamp: u64, value: M::Value) -> Vec<WindowResult<M::Value>> {
+        if self.released.is_some_and(|r| timestamp < r) {
+            self.late += 1;
+            return Vec::new();
+        }
+        self.pending.insert((timestamp, self.arrivals), value);
+        self.arrivals += 1;
+        let max_seen = self.max_seen.map_or(timestamp, |m| m.max(timestamp));
+        self.max_seen = Some(max_seen);
+
+        let mut out = Vec::new();
+        while let Some(entry) = self.pending.first_entry() {
+            if entry.key().0 > max_seen.saturating_sub(self.lateness) {
+                break;
+            }
+            let ((ts, _), value) = entry.remove_entry();
+            self.release(ts, value, &mut out);
+        }
+        out
+    }
+
+    /// Releases every pending event and closes every window that has events.
+    pub fn flush(&mut self) -> Vec<WindowResult<M::Value>> {
+        let mut out = Vec::new();
+        while let Some(((ts, _), value)) = self.pending.pop_first() {
+            self.release(ts, value, &mut out);
+        }
+        if self.policy != EmitPolicy::PerEvent {
+            self.close_windows(None, &mut out);
+        }
+        out
+    }
+
+    fn hop(&self) -> u64 {
+        match self.policy {
+            EmitPolicy::PerEvent | EmitPolicy::Tumbling => self.duration,
+            EmitPolicy::Hopping { hop } => hop,
+        }
+    }
+
+    fn release(&mut self, ts: u64, value: M::Value, out: &mut Vec<WindowResult<M::Value>>) {
+        self.released = Some(ts);
+        if self.policy == EmitPolicy::PerEvent {
+            self.push_event(ts, value);
+            self.evict_before(ts.saturating_sub(self.duration - 1));
+            out.push(WindowResult {
+                end: ts,
+                value: self.window.query(),
+            });
+        } else {
+            self.close_windows(Some(ts), out);
+            self.push_event(ts, value);
+            if self.next_end.is_none() {
+                let end = (ts / self.hop() + 1).checked_mul(self.hop());
+                self.saturated = end.is_none();
+                self.next_end = Some(end.unwrap_or(u64::MAX));
+            }
+        }
+    }
+
+    /// Emits every window ending at or before `upto`, or every window with
+    /// events if `upto` is `None`.
+    fn close_windows(&mut self, upto: Option<u64>, out: &mut Vec<WindowResult<M::Value>>) {
+        while let Some(end) = self.next_end {
+            if upto.is_some_and(|upto| end > upto || self.saturated) {
+                break;
+            }
+            self.evict_before(end.saturating_sub(self.duration));
+            if self.window.is_empty() {
+                self.next_end = None;
+                self.saturated = false;
+                break;
+            }
+            out.push(WindowResult {
+                end,
+                value: self.window.query(),
+            });
+            if self.saturated {
+                self.window.clear();
+                self.timestamps.clear();
+                self.next_end = None;
+                self.saturated = false;
+                break;
+            }
+            let next = end.checked_add(self.hop());
+            self.saturated = next.is_none();
+            self.next_end = Some(next.unwrap_or(u64::MAX));
+        }
+    }
+
+    fn push_event(&mut self, ts: u64, value: M::Value) {
+        self.window.push_back(value);
+        self.timestamps.push_back(ts);
+    }
+
+    fn evict_before(&mut self, start: u64) {
+        while self.timestamps.front().is_some_and(|ts| *ts < start) {
+            self.timestamps.pop_front();
+            self.window.pop_front();
+        }
+    }
+}
+
+/// For each event `(t, v)`, sorted by `t`, the maximum over the events in
+/// `(t - duration, t]`.
+pub fn time_window_max<T: Ord + Clone>(events: &[(u64, T)], duration: u64) -> Vec<(u64, T)> {
+    time_window_fold(events, duration, Max::new())
+}
+
+/// For each event `(t, v)`, sorted by `t`, the minimum over the events in
+/// `(t - duration, t]`.
+pub fn time_window_min<T: Ord + Clone>(events: &[(u64, T)], duration: u64) -> Vec<(u64, T)> {
+    time_window_fold(events, duration, Min::new())
+}
+
+fn time_window_fold<T, M>(events: &[(u64, T)], duration: u64, monoid: M) -> Vec<(u64, T)>
+where
+    T: Clone,
+    M: Monoid<Value = Option<T>>,
+{
+    let mut window = TimeWindow::new(monoid, duration);
+    events
+        .iter()
+        .flat_map(|(ts, v)| window.push(*ts, Some(v.clone())))
+        .map(|r| (r.end, r.value.expect("window contains the event")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Sum;
+    use test_rng::Rng;
+
+    #[test]
+    fn per_event_max_over_irregular_events() {
+        let events = [(0, 4), (1, 2), (3, 7), (5, 1), (9, 3), (10, 5)];
+        assert_eq!(
+            time_window_max(&events, 5),
+            vec![(0, 4), (1, 4), (3, 7), (5, 7), (9, 3), (10, 5)]
+        );
+        assert_eq!(
+            time_window_min(&events, 5),
+            vec![(0, 4), (1, 2), (3, 2), (5, 1), (9, 1), (10, 3)]
+        );
+    }
+
+    #[test]
+    fn tumbling_and_hopping_sums() {
+        let events = [(1, 1), (2, 2), (6, 3), (7, 4), (23, 5)];
+
+        let mut tumbling = TimeWindow::new(Sum::new(), 5).policy(EmitPolicy::Tumbling);
+        let mut out: Vec<_> = events
+            .iter()
+            .flat_map(|(ts, v)| tumbling.push(*ts, *v))
+            .collect();
+        out.extend(tumbling.flush());
+        let out: Vec<(u64, i32)> = out.into_iter().map(|r| (r.end, r.value)).collect();
+        assert_eq!(out, vec![(5, 3), (10, 7), (25, 5)]);
+
+        let mut hopping = TimeWindow::new(Sum::new(), 10).policy(EmitPolicy::Hopping { hop: 5 });
+        let mut out: Vec<_> = events
+            .iter()
+            .flat_map(|(ts, v)| hopping.push(*ts, *v))
+            .collect();
+        out.extend(hopping.flush());
+        let out: Vec<(u64, i32)> = out.into_iter().map(|r| (r.end, r.value)).collect();
+        assert_eq!(out, vec![(5, 3), (10, 10), (15, 7), (25, 5), (30, 5)]);
+    }
+
+    #[test]
+    fn out_of_order_within_lateness() {
+        let mut window = TimeWindow::new(Sum::new(), 3).lateness(2);
+        assert!(window.push(5, 1).is_empty());
+        assert!(window.push(4, 10).is_empty());
+        let out = window.push(7, 100);
+        assert_eq!(
+            out,
+            vec![
+                WindowResult { end: 4, value: 10 },
+                WindowResult { end: 5, value: 11 }
+            ]
+        );
+        // Older than the last released event.
+        assert!(window.push(3, 1000).is_empty());
+        assert_eq!(window.late_events(), 1);
+        assert_eq!(window.flush(), vec![WindowResult { end: 7, value: 101 }]);
+    }
+
+    #[test]
+    fn timestamps_near_the_end_of_time() {
+        let mut window = TimeWindow::new(Sum::new(), 2).lateness(1);
+        assert!(window.push(u64::MAX - 1, 1).is_empty());
+        let out = window.push(u64::MAX, 2);
+        assert_eq!(
+            out,
+            vec![WindowResult {
+                end: u64::MAX - 1,
+                value: 1
+            }]
+        );
+        assert_eq!(
+            window.flush(),
+            vec![WindowResult {
+                end: u64::MAX,
+                value: 3
+            }]
+        );
+
+        let mut window = TimeWindow::new(Sum::new(), 10).policy(EmitPolicy::Tumbling);
+        assert!(window.push(u64::MAX - 3, 1).is_empty());
+        assert!(window.push(u64::MAX, 2).is_empty());
+        assert_eq!(
+            window.flush(),
+            vec![WindowResult {
+                end: u64::MAX,
+                value: 3
+            }]
+        );
+
+        let hopping = EmitPolicy::Hopping { hop: 4 };
+        let mut window = TimeWindow::new(Sum::new(), 10).policy(hopping);
+        assert!(window.push(u64::MAX - 8, 1).is_empty());
+        assert_eq!(
+            window.push(u64::MAX, 2),
+            vec![
+                WindowResult {
+                    end: u64::MAX - 7,
+                    value: 1
+                },
+                WindowResult {
+                    end: u64::MAX - 3,
+                    value: 1
+                }
+            ]
+        );
+        assert_eq!(
+            window.flush(),
+            vec![WindowResult {
+                end: u64::MAX,
+                value: 3
+            }]
+        );
+        assert!(window.flush().is_empty());
+    }
+
+    #[test]
+    fn shuffled_input_matches_sorted_input() {
+        let mut rng = Rng::new(31);
+        for _ in 0..100 {
+            let len = rng.below(30) as usize + 1;
+            let mut events: Vec<(u64, i64)> = Vec::new();
+            let mut ts = 0;
+            for _ in 0..len {
+                ts += 1 + rng.below(3);
+                events.push((ts, rng.range(-10, 10)));
+            }
+            // Swap some disjoint pairs of neighbours, at most 3 time units apart.
+            let mut shuffled = events.clone();
+            for i in (1..len).step_by(2) {
+                if rng.below(2) == 0 {
+                    shuffled.swap(i, i - 1);
+                }
+            }
+            let expected: Vec<(u64, i64)> = events
+                .iter()
+                .map(|(t, _)| {
+                    let window = events.iter().filter(|(s, _)| s <= t && s + 4 > *t);
+                    (*t, window.map(|(_, v)| *v).max().unwrap())
+                })
+                .collect();
+
+            let mut window = TimeWindow::new(Max::new(), 4).lateness(6);
+            let mut out: Vec<_> = shuffled
+                .iter()
+                .flat_map(|(t, v)| window.push(*t, Some(*v)))
+                .collect();
+            out.extend(window.flush());
+            assert_eq!(window.late_events(), 0);
+            let ends: Vec<u64> = out.iter().map(|r| r.end).collect();
+            assert_eq!(ends, events.iter().map(|e| e.0).collect::<Vec<_>>());
+            let values: Vec<Option<i64>> = out.iter().map(|r| r.value).collect();
+            assert_eq!(
+                values,
+                expected.iter().map(|e| Some(e.1)).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::ops::Range;
+
+use crate::aggregate::{Monoid, SlidingWindowAggregator, Sum};
+use crate::monotonic::MonotonicDeque;
+
+/// FIFO window state the two-pointer drivers expand and shrink.
+pub trait Window<T> {
+    fn push_back(&mut self, el: T);
+    fn pop_front(&mut self);
+}
+
+impl<T: Ord> Window<T> for MonotonicDeque<T> {
+    fn push_back(&mut self, el: T) {
+        MonotonicDeque::push_back(self, el);
+    }
+
+    fn pop_front(&mut self) {
+        MonotonicDeque::pop_front(self);
+    }
+}
+
+impl<M: Monoid> Window<M::Value> for SlidingWindowAggregator<M> {
+    fn push_back(&mut self, el: M::Value) {
+        SlidingWindowAggregator::push_back(self, el);
+    }
+
+    fn pop_front(&mut self) {
+        SlidingWindowAggregator::pop_front(self);
+    }
+}
+
+/// Two windows fed the same elements, e.g. a max and a min deque.
+impl<T: Clone, A: Window<T>, B: Window<T>> Window<T> for (A, B) {
+    fn push_back(&mut self, el: T) {
+        self.0.push_back(el.clone());
+        self.1.push_back(el);
+    }
+
+    fn pop_front(&mut self) {
+        self.0.pop_front();
+        self.1.pop_front();
+    }
+}
+
+/// Longest non-empty range of `nums` whose window satisfies `valid`, the
+/// leftmost one on ties.
+///
+/// `valid` must hold for every sub-window of a valid window (for example
+/// "max - min <= d"), so the left pointer never has to move back. O(n) calls
+/// to `valid`.
+pub fn longest_valid_window<T, W, F>(
+    nums: &[T],
+    mut window: W,
+    mut valid: F,
+) -> Option<Range<usize>>
+where
+    T: Clone,
+    W: Window<T>,
+    F: FnMut(&W) -> bool,
+{
+    let mut best: Option<Range<usize>> = None;
+    let mut l = 0;
+    for (r, el) in nums.iter().enumerate() {
+        window.push_back(el.clone());
+        while l <= r && !valid(&window) {
+            window.pop_front();
+            l += 1;
+        }
+        if l <= r && best.as_ref().is_none_or(|b| r + 1 - l > b.len()) {
+            best = Some(l..r + 1);
+        }
+    }
+    best
+}
+
+/// Number of non-empty ranges of `nums` whose window satisfies `valid`,
+/// under the same assumption as [`longest_valid_window`].
+pub fn count_valid_windows<T, W, F>(nums: &[T], mut window: W, mut valid: F) -> u64
+where
+    T: Clone,
+    W: Window<T>,
+    F: FnMut(&W) -> bool,
+{
+    let mut count = 0;
+    let mut l = 0;
+    for (r, el) in nums.iter().enumerate() {
+        window.push_back(el.clone());
+        while l <= r && !valid(&window) {
+            window.pop_front();
+            l += 1;
+        }
+        count += (r + 1 - l) as u64;
+    }
+    count
+}
+
+/// Shortest non-empty range of `nums` whose window satisfies `valid`, the
+/// leftmost one on ties.
+///
+/// `valid` must hold for every window containing a valid window (for example
+/// "sum >= s" over non-negative elements).
+pub fn shortest_valid_window<T, W, F>(
+    nums: &[T],
+    mut window: W,
+    mut valid: F,
+) -> Option<Range<usize>>
+where
+    T: Clone,
+    W: Window<T>,
+    F: FnMut(&W) -> bool,
+{
+    let mut best: Option<Range<usize>> = None;
+    let mut l = 0;
+    for (r, el) in nums.iter().enumerate() {
+        window.push_back(el.clone());
+        while l <= r && valid(&window) {
+            if best.as_ref().is_none_or(|b| r + 1 - l < b.len()) {
+                best = Some(l..r + 1);
+            }
+            window.pop_front();
+            l += 1;
+        }
+    }
+    best
+}
+
+/// Longest range whose maximum and minimum differ by at most `d`. The
+/// difference is taken in `i128`, so it cannot overflow.
+pub fn longest_with_max_min_diff(nums: &[i64], d: u64) -> Option<Range<usize>> {
+    let window = (MonotonicDeque::max(), MonotonicDeque::min());
+    longest_valid_window(nums, window, |(max, min)| match (max.best(), min.best()) {
+        (Some(max), Some(min)) => *max as i128 - *min as i128 <= d as i128,
+        _ => true,
+    })
+}
+
+/// Shortest range of the non-negative `nums` with sum at least `s`.
+pub fn shortest_with_sum_at_least(nums: &[u64], s: u64) -> Option<Range<usize>> {
+    let window = SlidingWindowAggregator::new(Sum::new());
+    shortest_valid_window(nums, window, |sum| sum.query() >= s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_rng::Rng;
+
+    #[test]
+    fn leetcode_example_windows() {
+        let nums = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(longest_with_max_min_diff(&nums, 4), Some(4..8));
+        assert_eq!(longest_with_max_min_diff(&nums, 2), Some(0..2));
+        assert_eq!(longest_with_max_min_diff(&nums, 0), Some(0..1));
+        assert_eq!(longest_with_max_min_diff(&[], 0), None);
+        assert_eq!(
+            longest_with_max_min_diff(&[i64::MIN, i64::MAX], 0),
+            Some(0..1)
+        );
+        assert_eq!(
+            longest_with_max_min_diff(&[i64::MIN, i64::MAX], u64::MAX),
+            Some(0..2)
+        );
+
+        let window = (MonotonicDeque::max(), MonotonicDeque::min());
+        let count = count_valid_windows(&nums, window, |(max, min)| {
+            max.best().zip(min.best()).is_none_or(|(a, b)| a - b <= 4)
+        });
+        assert_eq!(count, 18);
+
+        assert_eq!(
+            shortest_with_sum_at_least(&[2, 3, 1, 2, 4, 3], 7),
+            Some(4..6)
+        );
+        assert_eq!(shortest_with_sum_at_least(&[1, 1, 1], 7), None);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Rng::new(36);
+        for _ in 0..200 {
+            let len = rng.below(25) as usize;
+            let nums = rng.vec(len, -10, 10);
+            let d = rng.below(12);
+            let ok = |r: &Range<usize>| {
+                let w = &nums[r.clone()];
+                w.iter().max().unwrap() - w.iter().min().unwrap() <= d as i64
+            };
+            let ranges: Vec<Range<usize>> = (0..len)
+                .flat_map(|l| (l + 1..=len).map(move |r| l..r))
+                .collect();
+
+            let longest = ranges.iter().filter(|r| ok(r)).map(|r| r.len()).max();
+            let got = longest_with_max_min_diff(&nums, d);
+            assert_eq!(got.clone().map(|r| r.len()), longest);
+            assert!(got.is_none_or(|r| ok(&r)));
+
+            let window = (MonotonicDeque::max(), MonotonicDeque::min());
+            let count = count_valid_windows(&nums, window, |(max, min)| {
+                max.best()
+                    .zip(min.best())
+                    .is_none_or(|(a, b)| a - b <= d as i64)
+            });
+            assert_eq!(count, ranges.iter().filter(|r| ok(r)).count() as u64);
+
+            let pos: Vec<u64> = nums.iter().map(|x| x.unsigned_abs()).collect();
+            let s = rng.below(40);
+            let shortest = ranges
+                .iter()
+                .filter(|r| pos[(*r).clone()].iter().sum::<u64>() >= s)
+                .map(|r| r.len())
+                .min();
+            assert_eq!(
+                shortest_with_sum_at_least(&pos, s).map(|r| r.len()),
+                shortest
+            );
+        }
+    }
+}